@@ -1,7 +1,8 @@
 use can_aerospace_lite::{
     driver::CANAerospaceDriver,
-    message::{CANAerospaceFrame, RawMessage},
-    types::{DataType, HardwareRevision, MessageType, SoftwareRevision},
+    message::{CANAerospaceFrame, CANAerospaceMessage, RawMessage},
+    service::ServiceResponse,
+    types::{DataType, HardwareRevision, MessageType, ServiceCodeEnum, SoftwareRevision},
     CANAerospaceLite,
 };
 
@@ -10,13 +11,16 @@ static mut COUNT: u32 = 0;
 struct CANDriver;
 
 impl CANAerospaceDriver for CANDriver {
-    fn send_frame(&mut self, frame: CANAerospaceFrame) {
+    type Error = ();
+
+    fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), ()> {
         println!("Send frame: {:#X?}", frame);
+        Ok(())
     }
 
-    fn recv_frame(&mut self) -> Option<CANAerospaceFrame> {
+    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, ()> {
         unsafe {
-            return match COUNT {
+            Ok(match COUNT {
                 1 => Some(CANAerospaceFrame {
                     message_type: MessageType::NSH(128),
                     message: RawMessage::from([
@@ -70,39 +74,42 @@ impl CANAerospaceDriver for CANDriver {
                     ]),
                 }),
                 _ => None,
-            };
+            })
         }
     }
 }
 
 fn main() {
+    let mut dds_handler = |request: &CANAerospaceMessage| {
+        println!("Request {:#X?}", request);
+        Some(ServiceResponse {
+            message_code: request.message_code,
+            data: DataType::ULONG(0xDEAD_BEEF),
+        })
+    };
     let mut can_aerospace: CANAerospaceLite<CANDriver> = CANAerospaceLite::new(10, CANDriver {});
     can_aerospace.set_hw_revision(HardwareRevision(0x02));
     can_aerospace.set_sw_revision(SoftwareRevision(0x01));
+    can_aerospace
+        .register_service_handler(ServiceCodeEnum::DDS, &mut dds_handler)
+        .unwrap();
 
-    can_aerospace.notify_receive_event();
+    can_aerospace.notify_receive_event().unwrap();
     unsafe {
         COUNT += 1;
     }
-    can_aerospace.notify_receive_event();
+    can_aerospace.notify_receive_event().unwrap();
     unsafe {
         COUNT += 1;
     }
-    can_aerospace.notify_receive_event();
+    can_aerospace.notify_receive_event().unwrap();
     unsafe {
         COUNT += 1;
     }
-    can_aerospace.notify_receive_event();
-    can_aerospace.notify_receive_event();
-
-    let mut message = can_aerospace.read_message().unwrap();
-    println!("Message {:#X?}", message);
-    message.message_type = MessageType::NSH(message.message_type.id() + 1);
-    message.data = DataType::ULONG(0xDEAD_BEEF);
-    can_aerospace.send_message(message);
+    can_aerospace.notify_receive_event().unwrap();
+    can_aerospace.notify_receive_event().unwrap();
 
-    message = can_aerospace.read_message().unwrap();
-    println!("Message {:#X?}", message);
-    message = can_aerospace.read_message().unwrap();
-    println!("Message {:#X?}", message);
+    while let Some(message) = can_aerospace.read_message() {
+        println!("Message {:#X?}", message);
+    }
 }
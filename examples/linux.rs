@@ -1,5 +1,5 @@
 use std::{
-    thread,
+    io, thread,
     time::{Duration, Instant},
 };
 
@@ -16,37 +16,35 @@ struct CANDriverLinux {
 }
 
 impl CANAerospaceDriver for CANDriverLinux {
-    fn send_frame(&mut self, frame: CANAerospaceFrame) {
+    type Error = io::Error;
+
+    fn send_frame(&mut self, frame: CANAerospaceFrame) -> io::Result<()> {
         let can_frame = from_canas_to_socketcan(frame);
-        match self.socket.write_frame(&can_frame) {
-            Ok(_) => {
-                println!("CAN frame({}) sent.", can_frame.id())
-            }
-            Err(e) => {
-                println!("Unable to send CAN frame. {:?}", e);
-            }
-        }
+        self.socket.write_frame(&can_frame)?;
+        println!("CAN frame({}) sent.", can_frame.id());
+        Ok(())
     }
 
-    fn recv_frame(&mut self) -> Option<CANAerospaceFrame> {
+    fn recv_frame(&mut self) -> io::Result<Option<CANAerospaceFrame>> {
         print!("Reading... ");
         match self.socket.read_frame() {
             Ok(frame) => {
                 let message_type = MessageType::from(frame.id() as u16);
                 let aero_frame = CANAerospaceFrame {
-                    message_type: message_type,
+                    message_type,
                     message: RawMessage::new(frame.data()).unwrap(),
                 };
                 println!(
                     "Frame {:?} Data: {:#X?}",
                     aero_frame.message_type, aero_frame.message
                 );
-                Some(aero_frame)
+                Ok(Some(aero_frame))
             }
-            Err(e) => {
-                println!("Error occured: {:?}", e);
-                None
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                println!("No frame available");
+                Ok(None)
             }
+            Err(e) => Err(e),
         }
     }
 }
@@ -65,15 +63,17 @@ fn from_canas_to_socketcan(aero_frame: CANAerospaceFrame) -> CANFrame {
 fn main() {
     let socket = CANSocket::open("vcan0").unwrap();
     socket.set_nonblocking(true).unwrap();
-    let driver = CANDriverLinux { socket: socket };
+    let driver = CANDriverLinux { socket };
     let mut can_aero = CANAerospaceLite::new(0xA, driver);
-    can_aero.send_message(CANAerospaceMessage::new(
-        MessageType::EED(0x0),
-        0xA,
-        0x0,
-        0x0,
-        DataType::ULONG(0xDEAD_BEEF),
-    ));
+    can_aero
+        .send_message(CANAerospaceMessage::new(
+            MessageType::EED(0x0),
+            0xA,
+            0x0,
+            0x0,
+            DataType::ULONG(0xDEAD_BEEF),
+        ))
+        .unwrap_or_else(|e| println!("Unable to send CAN frame. {:?}", e));
     let instant = Instant::now();
     let mut delta = Duration::ZERO;
     // Use `cangen vcan0 -g 1000 -I 80 -L 8 -D 0A000000DEADBEEF` to generate can messages
@@ -82,7 +82,9 @@ fn main() {
         if delta >= Duration::from_secs(10) {
             break;
         }
-        can_aero.notify_receive_event();
+        if let Err(e) = can_aero.notify_receive_event() {
+            println!("Error occured: {:?}", e);
+        }
         thread::sleep(Duration::from_secs(1));
         delta = instant.elapsed();
     }
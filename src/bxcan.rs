@@ -2,18 +2,111 @@
 //!
 //! All required conversions from/into bxcan is defined in this module to have seamless experience with bxcan
 
-use bxcan::{Data, Frame, Id, StandardId};
+use core::convert::Infallible;
+
+use bxcan::{filter::Mask16, Can, Data, FilterOwner, Frame, Id, Instance, StandardId};
 
 use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    filter::ID_MASK,
     message::{CANAerospaceFrame, RawMessage},
     types::MessageType,
+    CANAerospaceLite,
 };
 
+/// Number of 16 bit mask filters of the largest bxCAN filter bank configuration (28 banks with 2 filters)
+const MAX_HARDWARE_MASKS: usize = 56;
+
+/// Errors reported by the bxCAN implementation of [CANAerospaceDriver]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BxcanError {
+    /// All transmit mailboxes are occupied by frames with higher or equal priority
+    WouldBlock,
+    /// The frame is queued, but it pushed a pending frame of lower priority out of its mailbox which could not be
+    /// queued again and is lost. The frame must not be sent again.
+    Displaced,
+    /// Receive FIFO overrun occurred and at least one frame is lost
+    Overrun,
+}
+
+impl<I: Instance> CANAerospaceDriver for Can<I> {
+    type Error = BxcanError;
+
+    fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), BxcanError> {
+        transmit_displacing(&Frame::from(&frame), |frame| self.transmit(frame))
+    }
+
+    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, BxcanError> {
+        match self.receive() {
+            Ok(frame) => Ok(Some(CANAerospaceFrame::from(frame))),
+            Err(nb::Error::WouldBlock) => Ok(None),
+            Err(nb::Error::Other(_)) => Err(BxcanError::Overrun),
+        }
+    }
+}
+
+/// Queues `frame` with `transmit` and queues the frames which are pushed out of their mailbox again
+pub(crate) fn transmit_displacing(
+    frame: &Frame,
+    mut transmit: impl FnMut(&Frame) -> nb::Result<Option<Frame>, Infallible>,
+) -> Result<(), BxcanError> {
+    let mut displaced = match transmit(frame) {
+        Ok(displaced) => displaced,
+        Err(nb::Error::WouldBlock) => return Err(BxcanError::WouldBlock),
+        Err(nb::Error::Other(never)) => match never {},
+    };
+    // a displaced frame only fits into a mailbox of an even lower priority frame, so this ends
+    while let Some(frame) = displaced {
+        displaced = match transmit(&frame) {
+            Ok(displaced) => displaced,
+            Err(nb::Error::WouldBlock) => return Err(BxcanError::Displaced),
+            Err(nb::Error::Other(never)) => match never {},
+        };
+    }
+    Ok(())
+}
+
+impl<'a, I: FilterOwner, const N: usize> CANAerospaceLite<'a, Can<I>, N> {
+    /// Programs the acceptance filters into the bxCAN filter banks, so that rejected frames do not reach the
+    /// receive FIFO. Service channels are always accepted and dropped nodes are still filtered in software.
+    /// Must be called again after the filters are changed, e.g. by [crate::types::ServiceCodeEnum::FSS] requests.
+    ///
+    /// Returns [Error::TableFull] and leaves the filter banks unchanged if there are not enough banks.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.add_acceptance_filter(AcceptanceFilter::Class(MessageClass::NOD))?;
+    /// can_aerospace.program_filter_banks()?;
+    /// ```
+    pub fn program_filter_banks(&mut self) -> Result<(), Error<BxcanError>> {
+        let masks = self
+            .filters
+            .hardware_masks::<MAX_HARDWARE_MASKS>()
+            .ok_or(Error::TableFull)?;
+        let mut banks = self.driver.modify_filters();
+        if masks.len() > banks.num_banks() as usize * 2 {
+            return Err(Error::TableFull);
+        }
+        banks.clear();
+        let filter = |(id, mask): (u16, u16)| {
+            Mask16::frames_with_std_id(
+                StandardId::new(id & ID_MASK).unwrap_or(StandardId::ZERO),
+                StandardId::new(mask & ID_MASK).unwrap_or(StandardId::ZERO),
+            )
+        };
+        for (index, pair) in masks.chunks(2).enumerate() {
+            let second = *pair.last().unwrap_or(&pair[0]);
+            banks.enable_bank(index as u8, [filter(pair[0]), filter(second)]);
+        }
+        Ok(())
+    }
+}
+
 impl From<Frame> for CANAerospaceFrame {
     fn from(frame: Frame) -> Self {
         let raw_id = match frame.id() {
             Id::Standard(id) => id.as_raw() as u32,
-            Id::Extended(id) => id.as_raw() as u32,
+            Id::Extended(id) => id.as_raw(),
         };
         let message: RawMessage = match frame.data() {
             Some(data) => RawMessage::new(data).unwrap(),
@@ -0,0 +1,313 @@
+//! # CANAerospace - Service client
+//!
+//! Service requests are sent to other nodes with [CANAerospaceLite::send_request] on a [ServiceChannel].
+//! Responses received on the response identifier of the channel are matched to the pending requests by node,
+//! channel and service code. If several requests of the same service
+//! are pending, the one with the `message_code` of the response is preferred, otherwise the oldest one.
+//!
+//! Requests time out if no response is received within their timeout, measured from the time given when the
+//! request is sent to the time given to [CANAerospaceLite::poll_requests]. Both are read from the same clock of
+//! the application. Results are collected with [CANAerospaceLite::take_response].
+//!
+//! The nodes on the bus are discovered by [CANAerospaceLite::ids_scan], which broadcasts an IDS request and
+//! collects the responses of all nodes within a time window. Responses which a pending request waits for are
+//! not collected by the scan.
+
+use heapless::Vec;
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, IDSResponse, MessageType, ServiceChannel, ServiceCodeEnum, Timestamp},
+    CANAerospaceLite,
+};
+
+/// Maximum number of requests whose result is not taken yet
+pub const PENDING_REQUEST_TABLE_SIZE: usize = 8;
+
+/// Maximum number of nodes found by an IDS scan
+pub const SCAN_TABLE_SIZE: usize = 32;
+
+/// Identifies a request sent by [CANAerospaceLite::send_request]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RequestId(u16);
+
+/// State of a request sent by [CANAerospaceLite::send_request]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RequestStatus {
+    /// No response is received yet
+    Pending,
+    /// Response of the node
+    Answered(ServiceResponse),
+    /// No response is received within the timeout
+    TimedOut,
+}
+
+/// Node which answered an IDS scan
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScannedNode {
+    pub node_id: u8,
+    /// Identification of the first response of the node
+    pub identification: IDSResponse,
+    /// True if more than one node answered with this node ID
+    pub duplicate: bool,
+}
+
+/// Nodes found by an IDS scan
+pub type ScanTable = Vec<ScannedNode, SCAN_TABLE_SIZE>;
+
+/// IDS scan in progress or completed
+#[derive(Debug)]
+struct Scan {
+    response: MessageType,
+    window: Timestamp,
+    since: Timestamp,
+    complete: bool,
+    nodes: ScanTable,
+}
+
+/// Request sent to another node
+#[derive(Debug)]
+struct PendingRequest {
+    id: RequestId,
+    node_id: u8,
+    response: MessageType,
+    service_code: ServiceCodeEnum,
+    message_code: u8,
+    timeout: Timestamp,
+    since: Timestamp,
+    status: RequestStatus,
+}
+
+/// Requests sent by the node
+#[derive(Debug, Default)]
+pub(crate) struct ServiceClient {
+    requests: Vec<PendingRequest, PENDING_REQUEST_TABLE_SIZE>,
+    next_id: u16,
+    scan: Option<Scan>,
+}
+
+impl ServiceClient {
+    /// Adds a response to the running IDS scan, returns false if it does not belong to it
+    fn collect(&mut self, response: &CANAerospaceMessage) -> bool {
+        let Some(scan) = self.scan.as_mut() else {
+            return false;
+        };
+        if scan.complete
+            || response.message_type != scan.response
+            || response.service_code != ServiceCodeEnum::IDS
+        {
+            return false;
+        }
+        let Some(identification) = IDSResponse::from_data(response.data) else {
+            return false;
+        };
+        match scan
+            .nodes
+            .iter_mut()
+            .find(|node| node.node_id == response.node_id)
+        {
+            Some(node) => node.duplicate = true,
+            None => {
+                // nodes which do not fit into the table are not reported
+                let _ = scan.nodes.push(ScannedNode {
+                    node_id: response.node_id,
+                    identification,
+                    duplicate: false,
+                });
+            }
+        }
+        true
+    }
+
+    /// Stores the response to a pending request or the running scan, returns false if none is waiting for it.
+    /// Pending requests are answered first, so a scan on the same channel does not take their responses.
+    pub(crate) fn complete(&mut self, response: &CANAerospaceMessage) -> bool {
+        self.answer(response) || self.collect(response)
+    }
+
+    /// Stores the response to a pending request, returns false if no request is waiting for it
+    fn answer(&mut self, response: &CANAerospaceMessage) -> bool {
+        let mut waiting = self.requests.iter_mut().filter(|request| {
+            request.status == RequestStatus::Pending
+                && request.response == response.message_type
+                && request.node_id == response.node_id
+                && request.service_code == response.service_code
+        });
+        let Some(first) = waiting.next() else {
+            return false;
+        };
+        let request = match waiting.find(|request| request.message_code == response.message_code) {
+            Some(request) if first.message_code != response.message_code => request,
+            _ => first,
+        };
+        request.status = RequestStatus::Answered(ServiceResponse {
+            message_code: response.message_code,
+            data: response.data,
+        });
+        true
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sends a service request to `node_id` on `channel` at the time `now`. The request times out if no response
+    /// is received within `timeout` after `now`, 0 disables the timeout.
+    ///
+    /// Returns [Error::InvalidNodeId] for broadcasts, which can be answered by several nodes, and
+    /// [Error::TableFull] if the results of [PENDING_REQUEST_TABLE_SIZE] requests are not taken yet.
+    /// # Example
+    /// ```ignore
+    /// let channel = ServiceChannel::new(ChannelPriority::High, 0).unwrap();
+    /// let id = can_aerospace.send_request(0x20, channel, ServiceCodeEnum::IDS, 0, DataType::NODATA, 100, now_ms())?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn send_request(
+        &mut self,
+        node_id: u8,
+        channel: ServiceChannel,
+        service_code: ServiceCodeEnum,
+        message_code: u8,
+        data: DataType,
+        timeout: Timestamp,
+        now: Timestamp,
+    ) -> Result<RequestId, Error<D::Error>> {
+        if service_code == ServiceCodeEnum::UNKNOWN {
+            return Err(Error::InvalidServiceCode);
+        }
+        if node_id == 0 {
+            return Err(Error::InvalidNodeId);
+        }
+        if self.client.requests.is_full() {
+            return Err(Error::TableFull);
+        }
+        self.send_message(CANAerospaceMessage {
+            message_type: channel.request_id(),
+            node_id,
+            service_code,
+            message_code,
+            data,
+        })?;
+        let id = RequestId(self.client.next_id);
+        self.client.next_id = self.client.next_id.wrapping_add(1);
+        let request = PendingRequest {
+            id,
+            node_id,
+            response: channel.response_id(),
+            service_code,
+            message_code,
+            timeout,
+            since: now,
+            status: RequestStatus::Pending,
+        };
+        // capacity is checked before sending
+        let _ = self.client.requests.push(request);
+        Ok(id)
+    }
+
+    /// Returns the state of a request, None if it is unknown or its result is taken
+    pub fn request_status(&self, id: RequestId) -> Option<RequestStatus> {
+        self.client
+            .requests
+            .iter()
+            .find(|request| request.id == id)
+            .map(|request| request.status)
+    }
+
+    /// Returns the result of a request which is answered or timed out and forgets the request.
+    /// Returns [RequestStatus::Pending] while the request is waiting for its response.
+    /// # Example
+    /// ```ignore
+    /// if let Some(RequestStatus::Answered(response)) = can_aerospace.take_response(id) {
+    ///     // use response.data
+    /// }
+    /// ```
+    pub fn take_response(&mut self, id: RequestId) -> Option<RequestStatus> {
+        let index = self
+            .client
+            .requests
+            .iter()
+            .position(|request| request.id == id)?;
+        match self.client.requests[index].status {
+            RequestStatus::Pending => Some(RequestStatus::Pending),
+            _ => Some(self.client.requests.remove(index).status),
+        }
+    }
+
+    /// Forgets a request, a response received later is queued like any other message
+    pub fn cancel_request(&mut self, id: RequestId) {
+        self.client.requests.retain(|request| request.id != id);
+    }
+
+    /// Broadcasts an IDS request on `channel` at the time `now` and collects the responses of all nodes until
+    /// `window` elapsed, which is checked by [CANAerospaceLite::poll_requests]. A running scan is restarted.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.ids_scan(ServiceChannel::new(ChannelPriority::High, 0).unwrap(), 200, now_ms())?;
+    /// // call poll_requests periodically
+    /// if let Some(nodes) = can_aerospace.ids_scan_result() {
+    ///     for node in nodes.iter().filter(|node| node.duplicate) {
+    ///         // node ID is used more than once
+    ///     }
+    /// }
+    /// ```
+    pub fn ids_scan(
+        &mut self,
+        channel: ServiceChannel,
+        window: Timestamp,
+        now: Timestamp,
+    ) -> Result<(), Error<D::Error>> {
+        self.client.scan = None;
+        self.send_message(CANAerospaceMessage {
+            message_type: channel.request_id(),
+            node_id: 0,
+            service_code: ServiceCodeEnum::IDS,
+            message_code: 0,
+            data: DataType::NODATA,
+        })?;
+        self.client.scan = Some(Scan {
+            response: channel.response_id(),
+            window,
+            since: now,
+            complete: false,
+            nodes: ScanTable::new(),
+        });
+        Ok(())
+    }
+
+    /// Returns the nodes found by [CANAerospaceLite::ids_scan] in the order of their responses,
+    /// None while the scan is running or if no scan is started
+    pub fn ids_scan_result(&self) -> Option<&ScanTable> {
+        match &self.client.scan {
+            Some(scan) if scan.complete => Some(&scan.nodes),
+            _ => None,
+        }
+    }
+
+    /// Times out the pending requests without response and completes the IDS scan when its window elapsed.
+    /// Has to be called periodically while requests are pending, with `now` read from the clock which is
+    /// used to send them.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.poll_requests(now_ms());
+    /// ```
+    pub fn poll_requests(&mut self, now: Timestamp) {
+        if let Some(scan) = self.client.scan.as_mut() {
+            if now.wrapping_sub(scan.since) >= scan.window {
+                scan.complete = true;
+            }
+        }
+        for request in self.client.requests.iter_mut() {
+            if request.status != RequestStatus::Pending || request.timeout == 0 {
+                continue;
+            }
+            if now.wrapping_sub(request.since) >= request.timeout {
+                request.status = RequestStatus::TimedOut;
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! # CANAerospace - Configuration
+//!
+//! Settings which are changed by service requests can be kept in non-volatile memory by a [ConfigStore],
+//! so the node starts with them after the next power up.
+
+use crate::{
+    driver::CANAerospaceDriver,
+    types::{Bitrate, DataType, IDSConfiguration},
+    CANAerospaceLite,
+};
+
+/// Non-volatile memory of the node settings. Settings which are not stored are lost on power down.
+pub trait ConfigStore {
+    /// Stores the bit rate which is set by a [crate::types::ServiceCodeEnum::BSS] request
+    fn store_bitrate(&mut self, _bitrate: Bitrate) {}
+
+    /// Stores the node ID which is set by a [crate::types::ServiceCodeEnum::NIS] request
+    fn store_node_id(&mut self, _node_id: u8) {}
+
+    /// Stores the value of a parameter which is written by a [crate::types::ServiceCodeEnum::MCS] request
+    fn store_parameter(&mut self, _index: u8, _value: DataType) {}
+
+    /// Stores the remap of a logical identifier which is set by a [crate::types::ServiceCodeEnum::CSS] request,
+    /// equal identifiers remove the remap
+    fn store_remap(&mut self, _logical: u16, _actual: u16) {}
+
+    /// Stores the number of the identifier distribution which is activated by a
+    /// [crate::types::ServiceCodeEnum::DSS] request
+    fn store_id_distribution(&mut self, _configuration: IDSConfiguration) {}
+}
+
+/// Settings which are changed by service requests
+#[derive(Default)]
+pub(crate) struct Configuration<'a> {
+    pub(crate) store: Option<&'a mut dyn ConfigStore>,
+    /// Bit rate which is applied after the response to a BSS request is sent
+    pub(crate) pending_bitrate: Option<Bitrate>,
+    /// Node ID which is applied after the response to a NIS request is sent
+    pub(crate) pending_node_id: Option<u8>,
+}
+
+impl core::fmt::Debug for Configuration<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Configuration")
+            .field("store", &self.store.is_some())
+            .field("pending_bitrate", &self.pending_bitrate)
+            .field("pending_node_id", &self.pending_node_id)
+            .finish()
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sets the non-volatile memory which keeps the settings changed by service requests
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_config_store(&mut eeprom);
+    /// ```
+    pub fn set_config_store(&mut self, store: &'a mut dyn ConfigStore) {
+        self.config.store = Some(store);
+    }
+}
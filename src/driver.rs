@@ -2,13 +2,25 @@
 //!
 //! CANAerospace requires a driver to interract with CAN hardware
 
-use crate::message::CANAerospaceFrame;
+use crate::{message::CANAerospaceFrame, types::Bitrate};
 
 /// CANAerospaceDriver trait is act like a gate to hardware for CANAerospaceLite
 pub trait CANAerospaceDriver {
+    /// Error type reported by the hardware, e.g. full TX mailbox or bus-off state
+    type Error;
     /// Takes [CANAerospaceFrame] to send it using the hardware
-    fn send_frame(&mut self, frame: CANAerospaceFrame);
+    fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), Self::Error>;
     /// Returns Option<[CANAerospaceFrame]> if the value is None then no action will be taken.
     /// if the value is present then frame will be handled by [crate::CANAerospaceLite].
-    fn recv_frame(&mut self) -> Option<CANAerospaceFrame>;
+    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, Self::Error>;
+    /// Returns true if the hardware can be switched to `bitrate` by [CANAerospaceDriver::set_bitrate].
+    /// [crate::types::ServiceCodeEnum::BSS] requests are rejected while no bit rate is supported, which is the default.
+    fn supports_bitrate(&self, _bitrate: Bitrate) -> bool {
+        false
+    }
+    /// Reconfigures the bit rate of the hardware. It is called after the response to the
+    /// [crate::types::ServiceCodeEnum::BSS] request is handed over, so pending frames should be transmitted first.
+    fn set_bitrate(&mut self, _bitrate: Bitrate) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
@@ -0,0 +1,26 @@
+//! # CANAerospace - Error
+//!
+//! Errors which can be reported by [crate::CANAerospaceLite]
+
+/// Error type of [crate::CANAerospaceLite] operations.
+///
+/// `E` is the error type of the used [crate::driver::CANAerospaceDriver]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error<E> {
+    /// Driver failed to send or receive a frame
+    Driver(E),
+    /// Receive queue is full and the frame is rejected, see [crate::queue::OverflowPolicy::Reject]
+    QueueFull,
+    /// Message type can not be used for the requested operation
+    InvalidIdentifier,
+    /// Service code can not be used for the requested operation
+    InvalidServiceCode,
+    /// A fixed capacity table of the node has no free entry
+    TableFull,
+    /// Node ID 0 can not be used, it addresses all nodes
+    InvalidNodeId,
+    /// Parameter is not registered or the value does not match its type or limits
+    InvalidParameter,
+    /// Identifier distribution is not registered or can not be replaced
+    InvalidDistribution,
+}
@@ -0,0 +1,222 @@
+//! # CANAerospace - Acceptance filters
+//!
+//! Received frames which are not handled by the node are queued only if their identifier is accepted by one of the
+//! [AcceptanceFilter]s and they are not sent by a dropped node. This includes service responses which do not belong
+//! to a request of the node and requests on channels the node does not listen on. All frames are accepted while no
+//! filter is set.
+//! Filters are set by the application or remotely by [crate::types::ServiceCodeEnum::FSS] requests.
+
+use heapless::Vec;
+
+use crate::{
+    driver::CANAerospaceDriver, error::Error, message::CANAerospaceFrame, CANAerospaceLite,
+};
+
+/// Maximum number of acceptance filters
+pub const FILTER_TABLE_SIZE: usize = 16;
+/// Maximum number of nodes whose frames are dropped
+pub const DROPPED_NODES_SIZE: usize = 8;
+
+/// Identifier ranges of the message types
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageClass {
+    EED,
+    NSH,
+    UDH,
+    NOD,
+    UDL,
+    DSD,
+    NSL,
+}
+
+impl MessageClass {
+    /// Returns the first and last identifier of the class
+    pub fn range(&self) -> (u16, u16) {
+        match self {
+            MessageClass::EED => (0, 127),
+            MessageClass::NSH => (128, 199),
+            MessageClass::UDH => (200, 299),
+            MessageClass::NOD => (300, 1799),
+            MessageClass::UDL => (1800, 1899),
+            MessageClass::DSD => (1900, 1999),
+            MessageClass::NSL => (2000, 2031),
+        }
+    }
+}
+
+/// Accepts identifiers of received frames
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AcceptanceFilter {
+    /// Identifiers from `first` to `last`, both included
+    Range { first: u16, last: u16 },
+    /// Identifiers whose bits selected by `mask` are equal to the ones of `id`
+    Mask { id: u16, mask: u16 },
+    /// All identifiers of a message type
+    Class(MessageClass),
+}
+
+impl AcceptanceFilter {
+    /// Returns true if the identifier is accepted
+    ///```
+    /// # use can_aerospace_lite::filter::{AcceptanceFilter, MessageClass};
+    /// assert!(AcceptanceFilter::Mask { id: 0x130, mask: 0x7F0 }.matches(0x13F));
+    /// assert!(!AcceptanceFilter::Class(MessageClass::NOD).matches(200));
+    ///```
+    pub fn matches(&self, id: u16) -> bool {
+        match *self {
+            AcceptanceFilter::Range { first, last } => (first..=last).contains(&id),
+            AcceptanceFilter::Mask { id: filter, mask } => id & mask == filter & mask,
+            AcceptanceFilter::Class(class) => {
+                let (first, last) = class.range();
+                (first..=last).contains(&id)
+            }
+        }
+    }
+
+    /// Splits the filter into `(id, mask)` pairs which accept exactly the same identifiers
+    #[cfg(feature = "bxcan-support")]
+    pub(crate) fn masks(&self, mut mask: impl FnMut(u16, u16)) {
+        let (first, last) = match *self {
+            AcceptanceFilter::Mask { id, mask: bits } => return mask(id & bits, bits),
+            AcceptanceFilter::Range { first, last } => (first, last),
+            AcceptanceFilter::Class(class) => class.range(),
+        };
+        let (mut start, last) = (u32::from(first), u32::from(last));
+        while start <= last {
+            let mut size = 1;
+            while start % (size * 2) == 0 && start + size * 2 - 1 <= last {
+                size *= 2;
+            }
+            mask(start as u16, !(size - 1) as u16 & ID_MASK);
+            start += size;
+        }
+    }
+}
+
+/// All bits of a standard CAN identifier
+pub(crate) const ID_MASK: u16 = 0x7FF;
+
+/// Acceptance filters and dropped nodes of a node
+#[derive(Debug, Default)]
+pub(crate) struct FilterTable {
+    pub(crate) filters: Vec<AcceptanceFilter, FILTER_TABLE_SIZE>,
+    pub(crate) dropped_nodes: Vec<u8, DROPPED_NODES_SIZE>,
+}
+
+impl FilterTable {
+    /// Returns true if the frame has to be queued
+    pub(crate) fn accepts(&self, frame: &CANAerospaceFrame) -> bool {
+        if self.dropped_nodes.contains(&frame.message.node_id) {
+            return false;
+        }
+        let id = frame.message_type.id();
+        self.filters.is_empty() || self.filters.iter().any(|filter| filter.matches(id))
+    }
+
+    pub(crate) fn add(&mut self, filter: AcceptanceFilter) -> Result<(), FilterError> {
+        match filter {
+            AcceptanceFilter::Range { first, last } if first > last || last > ID_MASK => {
+                return Err(FilterError::Invalid)
+            }
+            AcceptanceFilter::Mask { id, mask } if id > ID_MASK || mask > ID_MASK => {
+                return Err(FilterError::Invalid)
+            }
+            _ => {}
+        }
+        if !self.filters.contains(&filter) {
+            self.filters
+                .push(filter)
+                .map_err(|_| FilterError::TableFull)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn drop_node(&mut self, node_id: u8) -> Result<(), FilterError> {
+        if !self.dropped_nodes.contains(&node_id) {
+            self.dropped_nodes
+                .push(node_id)
+                .map_err(|_| FilterError::TableFull)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.filters.clear();
+        self.dropped_nodes.clear();
+    }
+
+    /// Returns `(id, mask)` pairs which accept the same identifiers as the filters and all service requests,
+    /// None if more than `M` pairs are needed. Dropped nodes can not be filtered by identifiers.
+    #[cfg(feature = "bxcan-support")]
+    pub(crate) fn hardware_masks<const M: usize>(&self) -> Option<Vec<(u16, u16), M>> {
+        let mut masks = Vec::new();
+        if self.filters.is_empty() {
+            masks.push((0, 0)).ok()?;
+            return Some(masks);
+        }
+        let mut fits = true;
+        let service_channels = [
+            AcceptanceFilter::Class(MessageClass::NSH),
+            AcceptanceFilter::Class(MessageClass::NSL),
+        ];
+        for filter in service_channels.iter().chain(self.filters.iter()) {
+            filter.masks(|id, mask| fits &= masks.push((id, mask)).is_ok());
+        }
+        fits.then_some(masks)
+    }
+}
+
+/// Reasons of rejected filter changes
+pub(crate) enum FilterError {
+    Invalid,
+    TableFull,
+}
+
+impl<E> From<FilterError> for Error<E> {
+    fn from(error: FilterError) -> Self {
+        match error {
+            FilterError::Invalid => Error::InvalidIdentifier,
+            FilterError::TableFull => Error::TableFull,
+        }
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Adds an acceptance filter. Once a filter is set, only frames with accepted identifiers are queued.
+    /// Service requests handled by the node and responses to its own requests are not filtered.
+    /// Returns [Error::InvalidIdentifier] for ranges or masks beyond the 11 bit identifiers.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.add_acceptance_filter(AcceptanceFilter::Range { first: 300, last: 399 })?;
+    /// can_aerospace.add_acceptance_filter(AcceptanceFilter::Class(MessageClass::EED))?;
+    /// ```
+    pub fn add_acceptance_filter(
+        &mut self,
+        filter: AcceptanceFilter,
+    ) -> Result<(), Error<D::Error>> {
+        Ok(self.filters.add(filter)?)
+    }
+
+    /// Drops all frames which are sent by `node_id`, except service requests handled by the node and responses
+    /// to its own requests
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.drop_node(0x20)?;
+    /// ```
+    pub fn drop_node(&mut self, node_id: u8) -> Result<(), Error<D::Error>> {
+        Ok(self.filters.drop_node(node_id)?)
+    }
+
+    /// Removes all acceptance filters and dropped nodes, all frames are queued again
+    pub fn clear_filters(&mut self) {
+        self.filters.clear();
+    }
+
+    /// Returns the acceptance filters
+    pub fn acceptance_filters(&self) -> &[AcceptanceFilter] {
+        &self.filters.filters
+    }
+}
@@ -517,8 +517,8 @@ pub mod standard {
     }
 
     fn engine_status_id(engine_num: u16, status: u16) -> u16 {
-        let n = (engine_num - 1) as u16;
-        let s = (status - 1) as u16;
+        let n = engine_num - 1;
+        let s = status - 1;
         0x26C + ((s * 4) + n)
     }
 
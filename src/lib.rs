@@ -8,20 +8,41 @@
 
 #![no_std]
 // #![feature(doc_cfg)]
-use heapless::{binary_heap::Min, BinaryHeap};
+use heapless::LinearMap;
 
+use crate::client::ServiceClient;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::filter::FilterTable;
 use crate::message::{CANAerospaceFrame, CANAerospaceMessage};
+use crate::queue::{Overflow, OverflowPolicy, RxQueue};
+use crate::scheduler::Scheduler;
+use crate::service::{
+    css::RemapTable, dds::DataDownload, dss::IdDistributions, dus::DataUpload,
+    fps::FlashProgramming, mcs::ParameterTable, mis::ModuleTable, nss::NodeSynchronisation,
+    scs::Simulation, sts::StateTable, tcs::BuiltInTests, ServiceHandler, ServiceRegistry,
+    ServiceResponse, SERVICE_NOT_SUPPORTED,
+};
+use crate::statistics::{count, NodeStatistics};
 use crate::types::{
-    DataType, HardwareRevision, IDSConfiguration, IDSHeaderConfiguration, IDSResponse,
-    ServiceCodeEnum, SoftwareRevision,
+    ChannelPriority, DataType, HardwareRevision, IDSConfiguration, IDSHeaderConfiguration,
+    IDSResponse, MessageCode, ServiceChannel, ServiceCodeEnum, SoftwareRevision,
 };
 use crate::{driver::CANAerospaceDriver, types::MessageType};
 
+pub mod client;
+pub mod config;
 pub mod driver;
+pub mod error;
+pub mod filter;
 #[cfg(not(tarpaulin_include))]
 #[cfg(feature = "ids-standard")]
 pub mod id_distribution;
 pub mod message;
+pub mod queue;
+pub mod scheduler;
+pub mod service;
+pub mod statistics;
 mod tests;
 pub mod types;
 
@@ -38,10 +59,32 @@ pub use id_distribution as ids;
 pub const IDS_CONF_STANDARD: IDSConfiguration = IDSConfiguration(0);
 pub const IDS_MSG_HEADER_STANDARD: IDSHeaderConfiguration = 0;
 
+/// Receive queue capacity of [CANAerospaceLite] created by [CANAerospaceLite::new].
+pub const DEFAULT_RX_QUEUE_CAPACITY: usize = 10;
+
+/// Number of outgoing identifiers whose `message_code` is sequenced automatically by [CANAerospaceLite::send_message].
+pub const MESSAGE_CODE_TABLE_SIZE: usize = 32;
+
+/// Bits of all high and low priority service channels
+const ALL_SERVICE_CHANNELS: u64 =
+    (1 << (ServiceChannel::HIGH_PRIORITY_CHANNELS + ServiceChannel::LOW_PRIORITY_CHANNELS)) - 1;
+
+/// Returns the bit of a service channel, high priority channels come first
+fn channel_bit(channel: ServiceChannel) -> u64 {
+    let index = match channel.priority() {
+        ChannelPriority::High => channel.number(),
+        ChannelPriority::Low => ServiceChannel::HIGH_PRIORITY_CHANNELS + channel.number(),
+    };
+    1 << index
+}
+
 /// Main struct of the library. All logic is implemented around this struct.
 ///
 /// Must be initialized with `node_id`, `driver` which is a [CANAerospaceDriver]
 ///
+/// Received frames are queued until they are read, `N` is the capacity of this receive queue.
+/// Service requests are answered by the node, see [CANAerospaceLite::register_service_handler].
+///
 /// # Example
 ///```rust
 /// # use can_aerospace_lite::{CANAerospaceLite, driver::CANAerospaceDriver, message::{CANAerospaceFrame, RawMessage}, types::{DataType, HardwareRevision, MessageType, ServiceCodeEnum, SoftwareRevision}};
@@ -49,15 +92,17 @@ pub const IDS_MSG_HEADER_STANDARD: IDSHeaderConfiguration = 0;
 /// struct CANDriver;
 ///
 /// impl CANAerospaceDriver for CANDriver {
-///     fn send_frame(&mut self, frame: CANAerospaceFrame) {
+///     type Error = ();
+///     fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), ()> {
 ///        assert_eq!(frame.message_type.id(), MessageType::NSH(129).id());
+///        Ok(())
 ///     }
 ///     // implementation of send/recv frame...
-/// #    fn recv_frame(&mut self) -> Option<CANAerospaceFrame> {
+/// #    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, ()> {
 /// #        unsafe {
-/// #            return match COUNT {
+/// #            return Ok(match COUNT {
 /// #                1 => Some(CANAerospaceFrame {
-/// #                    message_type: MessageType::NSH(128),
+/// #                    message_type: MessageType::NSH(129),
 /// #                    message: RawMessage::from([10, DataType::ULONG(0).type_id(), 2, 3, 0xBA, 0xBA, 0xDE, 0xDE]),
 /// #                }),
 /// #                0 => Some(CANAerospaceFrame {
@@ -73,7 +118,7 @@ pub const IDS_MSG_HEADER_STANDARD: IDSHeaderConfiguration = 0;
 /// #                    message: RawMessage::from([10, DataType::NODATA.type_id(), 0, 3, 0xFB, 0xFB, 0xDE, 0xDE]),
 /// #                }),
 /// #                _ => None
-/// #            }
+/// #            })
 /// #        }
 /// #    }
 /// }
@@ -83,54 +128,98 @@ pub const IDS_MSG_HEADER_STANDARD: IDSHeaderConfiguration = 0;
 /// can_aerospace.set_hw_revision(HardwareRevision(0x02));
 /// can_aerospace.set_sw_revision(SoftwareRevision(0x01));
 ///
-/// can_aerospace.notify_receive_event(); // Notify function must be called when a can message is recieved/wanted to be received.
+/// can_aerospace.notify_receive_event().unwrap(); // Notify function must be called when a can message is recieved/wanted to be received.
 ///
 /// let mut message = can_aerospace.read_message().unwrap();
 /// // According to our driver we should receive NOD message
 /// assert_eq!(message.message_type.id(), MessageType::NOD(300).id());
 ///
 /// # unsafe { COUNT += 1; }
-/// # can_aerospace.notify_receive_event();
+/// # can_aerospace.notify_receive_event().unwrap();
 /// # unsafe { COUNT += 1; }
-/// # can_aerospace.notify_receive_event();
+/// # can_aerospace.notify_receive_event().unwrap();
 /// # unsafe { COUNT += 1; }
-/// # can_aerospace.notify_receive_event();
-/// # can_aerospace.notify_receive_event();
+/// # can_aerospace.notify_receive_event().unwrap();
+/// # can_aerospace.notify_receive_event().unwrap();
 /// message = can_aerospace.read_message().unwrap();
-/// assert_eq!(message.message_type.id(), MessageType::NSH(128).id());
+/// assert_eq!(message.message_type.id(), MessageType::NSH(129).id());
 ///
 /// message = can_aerospace.read_message().unwrap();
 /// assert_eq!(message.message_type.id(), MessageType::UDH(200).id());
 ///```
 ///
 #[derive(Debug)]
-pub struct CANAerospaceLite<D>
+pub struct CANAerospaceLite<'a, D, const N: usize = DEFAULT_RX_QUEUE_CAPACITY>
 where
     D: CANAerospaceDriver,
 {
-    pub node_id: u8,
+    node_id: u8,
     identification: IDSResponse,
-    nod_count: u8,
+    message_codes: LinearMap<u16, MessageCode, MESSAGE_CODE_TABLE_SIZE>,
+    stamp_node_id: bool,
     driver: D,
-    pub(crate) rx_queue: BinaryHeap<CANAerospaceFrame, Min, 10>,
+    pub(crate) rx_queue: RxQueue<N>,
+    statistics: NodeStatistics,
+    #[cfg(feature = "statistics-publish")]
+    statistics_publisher: statistics::StatisticsPublisher,
+    services: ServiceRegistry<'a>,
+    synchronisation: NodeSynchronisation<'a>,
+    download: DataDownload<'a>,
+    upload: DataUpload<'a>,
+    simulation: Simulation,
+    scheduler: Scheduler<'a>,
+    flash: FlashProgramming<'a>,
+    state: StateTable,
+    filters: FilterTable,
+    tests: BuiltInTests<'a>,
+    config: Configuration<'a>,
+    modules: ModuleTable,
+    parameters: ParameterTable,
+    remap: RemapTable,
+    distributions: IdDistributions<'a>,
+    client: ServiceClient,
+    /// Channels whose requests are served, see [channel_bit]
+    service_channels: u64,
 }
 
-impl<D> CANAerospaceLite<D>
+impl<'a, D> CANAerospaceLite<'a, D>
 where
     D: CANAerospaceDriver,
 {
-    /// Creates new instance of [CANAerospaceLite].
+    /// Creates new instance of [CANAerospaceLite] with a receive queue of [DEFAULT_RX_QUEUE_CAPACITY] frames.
     /// ```
     /// # use can_aerospace_lite::{CANAerospaceLite, driver::CANAerospaceDriver, message::{CANAerospaceFrame, RawMessage}, types::{DataType, HardwareRevision, MessageType, SoftwareRevision}};
     /// struct CANDriver;
     /// impl CANAerospaceDriver for CANDriver {
-    /// #    fn send_frame(&mut self, frame: CANAerospaceFrame) { todo!(); }
-    /// #    fn recv_frame(&mut self) -> Option<CANAerospaceFrame> { todo!(); }
+    /// #    type Error = ();
+    /// #    fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), ()> { todo!(); }
+    /// #    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, ()> { todo!(); }
     /// }
     /// let can_aerospace = CANAerospaceLite::new(0xFB, CANDriver{});
-    /// assert_eq!(can_aerospace.node_id, 0xFB);
+    /// assert_eq!(can_aerospace.node_id(), 0xFB);
     /// ```
     pub fn new(node_id: u8, driver: D) -> Self {
+        Self::with_capacity(node_id, driver)
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Creates new instance of [CANAerospaceLite] with a receive queue of `N` frames.
+    /// ```
+    /// # use can_aerospace_lite::{CANAerospaceLite, driver::CANAerospaceDriver, message::CANAerospaceFrame};
+    /// struct CANDriver;
+    /// impl CANAerospaceDriver for CANDriver {
+    /// #    type Error = ();
+    /// #    fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), ()> { todo!(); }
+    /// #    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, ()> { todo!(); }
+    /// }
+    /// let can_aerospace: CANAerospaceLite<_, 64> = CANAerospaceLite::with_capacity(0xFB, CANDriver{});
+    /// assert_eq!(can_aerospace.node_id(), 0xFB);
+    /// ```
+    pub fn with_capacity(node_id: u8, driver: D) -> Self {
         Self {
             node_id,
             identification: IDSResponse {
@@ -139,10 +228,56 @@ where
                 configuration: IDS_CONF_STANDARD,
                 header: IDS_MSG_HEADER_STANDARD,
             },
-            nod_count: 0,
+            message_codes: LinearMap::new(),
+            stamp_node_id: true,
             driver,
-            rx_queue: BinaryHeap::new(),
+            rx_queue: RxQueue::new(),
+            statistics: NodeStatistics::default(),
+            #[cfg(feature = "statistics-publish")]
+            statistics_publisher: statistics::StatisticsPublisher::default(),
+            services: ServiceRegistry::default(),
+            synchronisation: NodeSynchronisation::default(),
+            download: DataDownload::default(),
+            upload: DataUpload::default(),
+            simulation: Simulation::default(),
+            scheduler: Scheduler::default(),
+            flash: FlashProgramming::default(),
+            state: StateTable::default(),
+            filters: FilterTable::default(),
+            tests: BuiltInTests::default(),
+            config: Configuration::default(),
+            modules: ModuleTable::new(),
+            parameters: ParameterTable::new(),
+            remap: RemapTable::default(),
+            distributions: IdDistributions::default(),
+            client: ServiceClient::default(),
+            service_channels: ALL_SERVICE_CHANNELS,
+        }
+    }
+
+    /// Returns the node ID which is used for outgoing messages and to accept service requests
+    pub fn node_id(&self) -> u8 {
+        self.node_id
+    }
+
+    /// Changes the node ID, e.g. to the one loaded from the [config::ConfigStore] at start up.
+    /// Service requests are accepted and answered with the new ID from now on, DDS, DUS and STS transfers
+    /// addressed to the old ID are aborted. Node ID 0 is reserved for broadcasts.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_node_id(eeprom.node_id())?;
+    /// ```
+    pub fn set_node_id(&mut self, node_id: u8) -> Result<(), Error<D::Error>> {
+        if node_id == 0 {
+            return Err(Error::InvalidNodeId);
         }
+        if node_id != self.node_id {
+            self.abort_dds();
+            self.abort_dus();
+            self.abort_sts();
+            self.node_id = node_id;
+        }
+        Ok(())
     }
 
     /// Sets hardware revision information for response of [ServiceCodeEnum::IDS] service request.
@@ -163,13 +298,19 @@ where
         self.identification.sw_rev = rev;
     }
 
-    /// Sets configuration information for response of [ServiceCodeEnum::IDS] service request.
+    /// Activates the identifier distribution `conf`, which is reported in the response of [ServiceCodeEnum::IDS]
+    /// service request. Returns [Error::InvalidDistribution] unless it is [IDS_CONF_STANDARD] or registered by
+    /// [CANAerospaceLite::register_id_distribution].
     /// # Example
     /// ```ignore
-    /// can_aerospace.set_ids_configuration(IDS_CONF_STANDARD);
+    /// can_aerospace.set_ids_configuration(IDS_CONF_STANDARD)?;
     /// ```
-    pub fn set_ids_configuration(&mut self, conf: IDSConfiguration) {
+    pub fn set_ids_configuration(&mut self, conf: IDSConfiguration) -> Result<(), Error<D::Error>> {
+        if !self.distributions.contains(conf) {
+            return Err(Error::InvalidDistribution);
+        }
         self.identification.configuration = conf;
+        Ok(())
     }
 
     /// Sets message header information for response of [ServiceCodeEnum::IDS] service request.
@@ -181,21 +322,186 @@ where
         self.identification.header = conf;
     }
 
+    /// Sets the behaviour of the receive queue when a frame arrives while it is full.
+    /// Default policy is [OverflowPolicy::DropNewest].
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_overflow_policy(OverflowPolicy::DropLowestPriority);
+    /// ```
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.rx_queue.set_policy(policy);
+    }
+
+    /// Returns how many times a received frame did not fit into the receive queue.
+    pub fn rx_overflow_count(&self) -> u32 {
+        self.statistics.queue_overflows
+    }
+
+    /// Returns a snapshot of the node counters.
+    /// # Example
+    /// ```ignore
+    /// let sent = can_aerospace.statistics().frames_sent;
+    /// ```
+    pub fn statistics(&self) -> NodeStatistics {
+        self.statistics
+    }
+
+    /// Sets all node counters to zero.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.reset_statistics();
+    /// ```
+    pub fn reset_statistics(&mut self) {
+        self.statistics = NodeStatistics::default();
+    }
+
+    /// Registers a handler for requests of the service `code` which are addressed to this node.
+    /// A previously registered handler of the same code is replaced, also [ServiceCodeEnum::IDS]
+    /// can be overridden. Up to [service::SERVICE_HANDLER_TABLE_SIZE] handlers can be registered.
+    ///
+    /// Response of the handler is sent on the response channel of the request. Requests of
+    /// service codes without a handler are answered with [SERVICE_NOT_SUPPORTED] unless they are broadcasted.
+    /// # Example
+    /// ```ignore
+    /// let mut handler = |request: &CANAerospaceMessage| {
+    ///     Some(ServiceResponse { message_code: 0, data: request.data })
+    /// };
+    /// can_aerospace.register_service_handler(ServiceCodeEnum::CUSTOM(100), &mut handler)?;
+    /// ```
+    pub fn register_service_handler(
+        &mut self,
+        code: ServiceCodeEnum,
+        handler: &'a mut dyn ServiceHandler,
+    ) -> Result<(), Error<D::Error>> {
+        if code == ServiceCodeEnum::UNKNOWN {
+            return Err(Error::InvalidServiceCode);
+        }
+        self.services
+            .register(code, handler)
+            .map_err(|_| Error::TableFull)
+    }
+
+    /// Removes the handler of the service `code` and returns it.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.unregister_service_handler(ServiceCodeEnum::CUSTOM(100));
+    /// ```
+    pub fn unregister_service_handler(
+        &mut self,
+        code: ServiceCodeEnum,
+    ) -> Option<&'a mut dyn ServiceHandler> {
+        self.services.unregister(code)
+    }
+
+    /// Serves service requests only on `channels`, requests on other channels are queued like other messages.
+    /// Requests on all channels are served by default.
+    /// # Example
+    /// ```ignore
+    /// let channel = ServiceChannel::new(ChannelPriority::High, 0).unwrap();
+    /// can_aerospace.set_service_channels(&[channel]);
+    /// ```
+    pub fn set_service_channels(&mut self, channels: &[ServiceChannel]) {
+        self.service_channels = channels
+            .iter()
+            .fold(0, |mask, &channel| mask | channel_bit(channel));
+    }
+
+    /// Serves service requests on all channels again
+    pub fn listen_on_all_channels(&mut self) {
+        self.service_channels = ALL_SERVICE_CHANNELS;
+    }
+
+    /// Returns true if service requests on the channel are served
+    pub fn listens_on_channel(&self, channel: ServiceChannel) -> bool {
+        self.service_channels & channel_bit(channel) != 0
+    }
+
+    /// Enables or disables overwriting `node_id` of outgoing messages with own `node_id`.
+    /// Enabled by default, gateways which forward traffic of other nodes should disable it.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_node_id_stamping(false);
+    /// ```
+    pub fn set_node_id_stamping(&mut self, enabled: bool) {
+        self.stamp_node_id = enabled;
+    }
+
     /// Sends a CAN message using driver.
+    ///
+    /// `node_id` of [MessageType::EED], [MessageType::NOD], [MessageType::UDH], [MessageType::UDL]
+    /// and [MessageType::DSD] messages is overwritten with own `node_id` unless it is disabled by
+    /// [CANAerospaceLite::set_node_id_stamping]. Service messages keep the given target `node_id`.
+    ///
+    /// `message_code` of [MessageType::NOD], [MessageType::UDH] and [MessageType::UDL] messages is
+    /// overwritten with a rolling counter which is kept per CAN identifier and wraps at 255.
+    /// Up to [MESSAGE_CODE_TABLE_SIZE] identifiers are tracked, messages of further identifiers keep
+    /// the given `message_code`. All other message types are sent with the given `message_code`.
+    ///
+    /// Data of identifiers in simulation mode is replaced by the injected value, see [CANAerospaceLite::set_simulation].
+    /// Identifiers are sent on their actual identifier, see [CANAerospaceLite::actual_identifier].
     /// # Example
     /// ```ignore
     /// let m = CANAerospaceMessage {
     ///     message_type: MessageType::NOD(300),
-    ///     node_id: can_aerospace.node_id, // personal id for NOD messages
+    ///     node_id: 0, // will be replaced with can_aerospace.node_id()
     ///     service_code: ServiceCodeEnum::UNKNOWN,
     ///     message_code: 0,
     ///     data: DataType::ULONG(0xDEAD_BEEF),
     /// };
-    /// can_aerospace.send_message(m);
+    /// can_aerospace.send_message(m)?;
     /// ```
-    pub fn send_message(&mut self, message: CANAerospaceMessage) {
-        // TODO: overwrite node id of the messages according to message type
-        self.driver.send_frame(CANAerospaceFrame::from(message));
+    pub fn send_message(&mut self, message: CANAerospaceMessage) -> Result<(), Error<D::Error>> {
+        self.forward_message(message).map(|_| ())
+    }
+
+    /// Sends a CAN message like [CANAerospaceLite::send_message] and returns false if it is not handed to the
+    /// driver, because its identifier is simulated without an injected value
+    pub(crate) fn forward_message(
+        &mut self,
+        mut message: CANAerospaceMessage,
+    ) -> Result<bool, Error<D::Error>> {
+        if self.stamp_node_id {
+            match message.message_type {
+                MessageType::NSH(_) | MessageType::NSL(_) | MessageType::INVALID => {}
+                _ => message.node_id = self.node_id,
+            }
+        }
+        self.state.update(message.message_type, message.data);
+        match self.simulation.apply(message.message_type, message.data) {
+            Some(data) => message.data = data,
+            None => return Ok(false),
+        }
+        message.message_type = self.actual_identifier(message.message_type);
+        if let Some(code) = self.next_message_code(message.message_type) {
+            message.message_code = code;
+        }
+        match self.driver.send_frame(CANAerospaceFrame::from(message)) {
+            Ok(()) => {
+                count(&mut self.statistics.frames_sent);
+                Ok(true)
+            }
+            Err(e) => {
+                count(&mut self.statistics.driver_errors);
+                Err(Error::Driver(e))
+            }
+        }
+    }
+
+    /// Returns the next `message_code` of the given identifier and advances its counter.
+    /// Returns None for message types which are not sequenced or if the table is full.
+    fn next_message_code(&mut self, message_type: MessageType) -> Option<MessageCode> {
+        match message_type {
+            MessageType::NOD(id) | MessageType::UDH(id) | MessageType::UDL(id) => {
+                if let Some(code) = self.message_codes.get_mut(&id) {
+                    *code = code.wrapping_add(1);
+                    Some(*code)
+                } else {
+                    self.message_codes.insert(id, 0).ok()?;
+                    Some(0)
+                }
+            }
+            _ => None,
+        }
     }
 
     /// Reads a CAN message using driver.
@@ -212,53 +518,158 @@ where
 
     /// Notifies CANAerospace regarding the arrival of new CAN frame.
     /// This function must be called each time when a new message comes.
-    pub fn notify_receive_event(&mut self) {
-        if let Some(frame) = self.driver.recv_frame() {
+    ///
+    /// Returns an error if the driver fails to receive the frame or to send the response of a service request.
+    /// Returns [Error::QueueFull] if the frame is rejected by [OverflowPolicy::Reject].
+    pub fn notify_receive_event(&mut self) -> Result<(), Error<D::Error>> {
+        let received = self.driver.recv_frame().map_err(|e| {
+            count(&mut self.statistics.driver_errors);
+            Error::Driver(e)
+        })?;
+        if let Some(frame) = received {
+            count(&mut self.statistics.frames_received);
             match frame.message_type {
-                types::MessageType::NSH(id) | types::MessageType::NSL(id) => {
-                    if id % 2 == 0 {
-                        // Event ids are requests
-                        self.handle_service_request(frame);
-                    } else {
-                        self.rx_queue.push(frame).unwrap_or(());
+                types::MessageType::NSH(_) | types::MessageType::NSL(_) => {
+                    match ServiceChannel::from_request_id(frame.message_type) {
+                        Some(channel) if self.listens_on_channel(channel) => {
+                            self.handle_service_request(frame)?;
+                        }
+                        // Requests on channels without services are queued like other messages
+                        Some(_) => {
+                            if self.enqueue(frame)? {
+                                count(&mut self.statistics.service_requests_queued);
+                            }
+                        }
+                        None => {
+                            if !self.handle_service_response(&frame) {
+                                self.enqueue(frame)?;
+                            }
+                        }
                     }
                 }
-                types::MessageType::INVALID => { /* do nothing */ }
+                types::MessageType::INVALID => {
+                    count(&mut self.statistics.invalid_frames_discarded);
+                }
                 _ => {
-                    self.rx_queue.push(frame).unwrap_or(());
+                    self.enqueue(frame)?;
                 }
             };
         }
+        Ok(())
     }
 
-    /// Handles all the service requests and filters them according to `node_id`
-    fn handle_service_request(&mut self, frame: CANAerospaceFrame) {
-        if frame.message.node_id == self.node_id || frame.message.node_id == 0 {
-            match ServiceCodeEnum::from(frame.message.service_code) {
-                ServiceCodeEnum::IDS => {
-                    let response_message_type = match frame.message_type {
-                        MessageType::NSH(id) => MessageType::NSH(id + 1),
-                        MessageType::NSL(id) => MessageType::NSL(id + 1),
-                        _ => frame.message_type,
-                    };
-                    let message = CANAerospaceMessage {
-                        message_type: response_message_type,
-                        node_id: self.node_id,
-                        service_code: ServiceCodeEnum::IDS,
-                        message_code: frame.message.message_code,
-                        data: DataType::UCHAR4(
-                            self.identification.hw_rev.0,
-                            self.identification.sw_rev.0,
-                            self.identification.configuration.0,
-                            self.identification.header,
-                        ),
-                    };
-                    self.send_message(message);
-                }
-                _ => {
-                    self.rx_queue.push(frame).unwrap_or(());
+    /// Puts the frame into the receive queue according to the acceptance filters and the overflow policy.
+    /// Remapped identifiers and the ones of the active distribution are queued with their logical identifier.
+    /// Returns false if the frame is dropped.
+    fn enqueue(&mut self, mut frame: CANAerospaceFrame) -> Result<bool, Error<D::Error>> {
+        if !self.filters.accepts(&frame) {
+            count(&mut self.statistics.frames_filtered);
+            return Ok(false);
+        }
+        frame.message_type = self.logical_identifier(frame.message_type);
+        match self.rx_queue.push(frame) {
+            Ok(()) => Ok(true),
+            Err(overflow) => {
+                count(&mut self.statistics.queue_overflows);
+                match overflow {
+                    Overflow::DroppedQueued => Ok(true),
+                    Overflow::DroppedIncoming => Ok(false),
+                    Overflow::Rejected => Err(Error::QueueFull),
                 }
-            };
+            }
         }
     }
+
+    /// Handles the responses to requests sent by this node, returns false if the response is not expected
+    fn handle_service_response(&mut self, frame: &CANAerospaceFrame) -> bool {
+        let response = CANAerospaceMessage::from(frame.clone());
+        self.handle_upload_response(&response) || self.client.complete(&response)
+    }
+
+    /// Handles all the service requests and filters them according to `node_id`
+    fn handle_service_request(&mut self, frame: CANAerospaceFrame) -> Result<(), Error<D::Error>> {
+        if frame.message.node_id != self.node_id && frame.message.node_id != 0 {
+            return Ok(());
+        }
+        let request = CANAerospaceMessage::from(frame);
+        if let Some(handler) = self.services.get(request.service_code) {
+            if let Some(response) = handler.handle(&request) {
+                self.respond(&request, response)?;
+            }
+            count(&mut self.statistics.service_requests_handled);
+            return Ok(());
+        }
+        let response = match request.service_code {
+            ServiceCodeEnum::IDS => Some(ServiceResponse {
+                message_code: request.message_code,
+                data: DataType::from(self.identification),
+            }),
+            ServiceCodeEnum::NSS if self.synchronisation.time_base.is_some() => {
+                self.handle_nss(&request)
+            }
+            ServiceCodeEnum::DDS if self.download.target.is_some() || self.flash.active => {
+                self.handle_dds(&request)
+            }
+            ServiceCodeEnum::DUS if self.upload.source.is_some() => {
+                return self.handle_dus(&request)
+            }
+            ServiceCodeEnum::SCS if self.simulation.enabled => self.handle_scs(&request),
+            ServiceCodeEnum::TIS => self.handle_tis(&request),
+            ServiceCodeEnum::STS => {
+                self.handle_sts(&request);
+                None
+            }
+            ServiceCodeEnum::FSS => self.handle_fss(&request),
+            ServiceCodeEnum::TCS => self.handle_tcs(&request),
+            ServiceCodeEnum::BSS => self.handle_bss(&request),
+            ServiceCodeEnum::NIS => self.handle_nis(&request),
+            ServiceCodeEnum::MIS if !self.modules.is_empty() => self.handle_mis(&request),
+            ServiceCodeEnum::MCS if !self.parameters.is_empty() => self.handle_mcs(&request),
+            ServiceCodeEnum::CSS => self.handle_css(&request),
+            ServiceCodeEnum::DSS => self.handle_dss(&request),
+            ServiceCodeEnum::FPS if self.flash.is_supported() => self.handle_fps(&request),
+            // Broadcasted requests are not answered, otherwise all nodes would respond at once
+            _ if request.node_id != 0 => Some(ServiceResponse {
+                message_code: SERVICE_NOT_SUPPORTED,
+                data: DataType::NODATA,
+            }),
+            _ => None,
+        };
+        // Bit rate and node ID are switched only after the response is sent with the current ones
+        let bitrate = self.config.pending_bitrate.take();
+        let node_id = self.config.pending_node_id.take();
+        if let Some(response) = response {
+            self.respond(&request, response)?;
+            if request.service_code == ServiceCodeEnum::IDS {
+                count(&mut self.statistics.ids_requests_answered);
+            }
+        }
+        if let Some(bitrate) = bitrate {
+            self.apply_bitrate(bitrate)?;
+        }
+        if let Some(node_id) = node_id {
+            self.apply_node_id(node_id);
+        }
+        Ok(())
+    }
+
+    /// Sends the response of the request on the response channel of the request
+    fn respond(
+        &mut self,
+        request: &CANAerospaceMessage,
+        response: ServiceResponse,
+    ) -> Result<(), Error<D::Error>> {
+        let message_type = match ServiceChannel::from_request_id(request.message_type) {
+            Some(channel) => channel.response_id(),
+            None => request.message_type,
+        };
+        let message = CANAerospaceMessage {
+            message_type,
+            node_id: self.node_id,
+            service_code: request.service_code,
+            message_code: response.message_code,
+            data: response.data,
+        };
+        self.send_message(message)
+    }
 }
@@ -14,6 +14,7 @@ pub struct CANAerospaceMessage {
 
     /// If `message_type` is one of [MessageType::NSH] or [MessageType::NSL]
     /// then it indicates the target, otherwise it indicates the sender node
+    /// which is assigned by [crate::CANAerospaceLite::send_message]
     pub node_id: NodeId,
 
     /// Indicates service code for service messages
@@ -0,0 +1,136 @@
+//! # CANAerospace - Queue
+//!
+//! Receive queue of [crate::CANAerospaceLite] which orders frames by priority of their CAN identifier
+
+use core::cmp::Ordering;
+
+use heapless::{binary_heap::Min, BinaryHeap};
+
+use crate::message::CANAerospaceFrame;
+
+/// Behaviour of the receive queue when a frame arrives while the queue is full
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverflowPolicy {
+    /// Incoming frame is dropped
+    DropNewest,
+    /// Frame with the lowest priority (highest CAN identifier) is dropped, either a queued one or the incoming one
+    DropLowestPriority,
+    /// Incoming frame is dropped and [crate::error::Error::QueueFull] is reported
+    Reject,
+}
+
+/// Result of a push into a full receive queue
+#[derive(Debug)]
+pub(crate) enum Overflow {
+    /// Incoming frame is dropped
+    DroppedIncoming,
+    /// Incoming frame is queued in place of a dropped lower priority frame
+    DroppedQueued,
+    /// Incoming frame is rejected by [OverflowPolicy::Reject]
+    Rejected,
+}
+
+/// Frame in the receive queue, ordered by CAN identifier and then by arrival
+#[derive(Debug)]
+struct QueuedFrame {
+    pub(crate) sequence: u32,
+    frame: CANAerospaceFrame,
+}
+
+impl Ord for QueuedFrame {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Arrival order is compared with wrapping arithmetic, so it stays correct when the counter overflows
+        let arrival = (self.sequence.wrapping_sub(other.sequence) as i32).cmp(&0);
+        self.frame.cmp(&other.frame).then(arrival)
+    }
+}
+
+impl PartialOrd for QueuedFrame {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedFrame {}
+
+/// Priority queue of received frames with a capacity of `N` frames.
+///
+/// Frames with the same CAN identifier are returned in the order of their arrival.
+#[derive(Debug)]
+pub(crate) struct RxQueue<const N: usize> {
+    heap: BinaryHeap<QueuedFrame, Min, N>,
+    policy: OverflowPolicy,
+    pub(crate) sequence: u32,
+}
+
+impl<const N: usize> RxQueue<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            policy: OverflowPolicy::DropNewest,
+            sequence: 0,
+        }
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<CANAerospaceFrame> {
+        self.heap.pop().map(|queued| queued.frame)
+    }
+
+    /// Queues the frame according to the overflow policy.
+    /// Returns [Overflow] if the queue is full.
+    pub(crate) fn push(&mut self, frame: CANAerospaceFrame) -> Result<(), Overflow> {
+        let queued = QueuedFrame {
+            sequence: self.sequence,
+            frame,
+        };
+        let queued = match self.heap.push(queued) {
+            Ok(()) => {
+                self.sequence = self.sequence.wrapping_add(1);
+                return Ok(());
+            }
+            Err(queued) => queued,
+        };
+        match self.policy {
+            OverflowPolicy::DropNewest => Err(Overflow::DroppedIncoming),
+            OverflowPolicy::Reject => Err(Overflow::Rejected),
+            OverflowPolicy::DropLowestPriority => {
+                // Incoming frame is the newest one, so it is dropped if its identifier is not lower
+                let lowest = self.heap.iter().max();
+                if matches!(lowest, Some(lowest) if lowest.frame > queued.frame) {
+                    self.drop_lowest_priority();
+                    self.heap.push(queued).unwrap_or(());
+                    self.sequence = self.sequence.wrapping_add(1);
+                    Err(Overflow::DroppedQueued)
+                } else {
+                    Err(Overflow::DroppedIncoming)
+                }
+            }
+        }
+    }
+
+    /// Removes the frame with the highest CAN identifier from the queue
+    fn drop_lowest_priority(&mut self) {
+        let mut kept = BinaryHeap::new();
+        while self.heap.len() > 1 {
+            if let Some(queued) = self.heap.pop() {
+                kept.push(queued).unwrap_or(());
+            }
+        }
+        self.heap = kept;
+    }
+}
@@ -0,0 +1,175 @@
+//! # Cyclic transmission
+//!
+//! Output identifiers are registered with a [DataSource] and a transmission interval. [CANAerospaceLite::poll]
+//! samples the sources and sends the messages which are due. Intervals can be changed remotely by
+//! [crate::types::ServiceCodeEnum::TIS] requests.
+
+use heapless::LinearMap;
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    types::{DataType, MessageType, ServiceCodeEnum, Timestamp},
+    CANAerospaceLite,
+};
+
+/// Number of identifiers which can be transmitted cyclically
+pub const SCHEDULE_TABLE_SIZE: usize = 16;
+
+/// Provides the data of a cyclically transmitted identifier
+pub trait DataSource {
+    /// Returns the current value which is transmitted
+    fn sample(&mut self) -> DataType;
+}
+
+impl<F> DataSource for F
+where
+    F: FnMut() -> DataType,
+{
+    fn sample(&mut self) -> DataType {
+        self()
+    }
+}
+
+/// Cyclically transmitted identifier
+pub(crate) struct Scheduled<'a> {
+    pub(crate) message_type: MessageType,
+    pub(crate) interval: Timestamp,
+    pub(crate) last: Option<Timestamp>,
+    pub(crate) source: &'a mut dyn DataSource,
+}
+
+/// Keeps which identifiers are transmitted when
+#[derive(Default)]
+pub(crate) struct Scheduler<'a> {
+    pub(crate) entries: LinearMap<u16, Scheduled<'a>, SCHEDULE_TABLE_SIZE>,
+}
+
+impl core::fmt::Debug for Scheduler<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(id, entry)| (id, entry.interval)))
+            .finish()
+    }
+}
+
+impl<'a> Scheduler<'a> {
+    /// Changes the interval of a scheduled identifier, returns false if it is not scheduled.
+    /// An identifier which is enabled again is sent with the next poll.
+    pub(crate) fn set_interval(&mut self, id: u16, interval: Timestamp) -> bool {
+        match self.entries.get_mut(&id) {
+            Some(entry) => {
+                if entry.interval == 0 {
+                    entry.last = None;
+                }
+                entry.interval = interval;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Transmits the value of `source` on `message_type` every `interval`, 0 disables the transmission.
+    /// The identifier must be a [MessageType::NOD], [MessageType::UDH] or [MessageType::UDL] identifier,
+    /// scheduling it again replaces the source and the interval.
+    /// # Example
+    /// ```ignore
+    /// let mut altitude = || DataType::FLOAT(sensor.altitude());
+    /// can_aerospace.schedule(MessageType::NOD(315), 20, &mut altitude)?;
+    /// ```
+    pub fn schedule(
+        &mut self,
+        message_type: MessageType,
+        interval: Timestamp,
+        source: &'a mut dyn DataSource,
+    ) -> Result<(), Error<D::Error>> {
+        let id = message_type.output_id().ok_or(Error::InvalidIdentifier)?;
+        let entry = Scheduled {
+            message_type,
+            interval,
+            last: None,
+            source,
+        };
+        self.scheduler
+            .entries
+            .insert(id, entry)
+            .map_err(|_| Error::TableFull)?;
+        Ok(())
+    }
+
+    /// Stops the cyclic transmission of `message_type` and gives back its source
+    pub fn unschedule(&mut self, message_type: MessageType) -> Option<&'a mut dyn DataSource> {
+        let id = message_type.output_id()?;
+        self.scheduler.entries.remove(&id).map(|entry| entry.source)
+    }
+
+    /// Changes the transmission interval of a scheduled identifier, 0 disables the transmission
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_interval(MessageType::NOD(315), 100)?;
+    /// ```
+    pub fn set_interval(
+        &mut self,
+        message_type: MessageType,
+        interval: Timestamp,
+    ) -> Result<(), Error<D::Error>> {
+        let id = message_type.output_id().ok_or(Error::InvalidIdentifier)?;
+        if self.scheduler.set_interval(id, interval) {
+            Ok(())
+        } else {
+            Err(Error::InvalidIdentifier)
+        }
+    }
+
+    /// Returns the transmission interval of a scheduled identifier
+    pub fn interval(&self, message_type: MessageType) -> Option<Timestamp> {
+        let id = message_type.output_id()?;
+        self.scheduler.entries.get(&id).map(|entry| entry.interval)
+    }
+
+    /// Sends all scheduled identifiers whose interval has elapsed since their last transmission.
+    /// Must be called periodically with the current time.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.poll(now_ms())?;
+    /// ```
+    pub fn poll(&mut self, now: Timestamp) -> Result<(), Error<D::Error>> {
+        let ids = self
+            .scheduler
+            .entries
+            .keys()
+            .copied()
+            .collect::<heapless::Vec<u16, SCHEDULE_TABLE_SIZE>>();
+        for id in ids {
+            let Some(entry) = self.scheduler.entries.get_mut(&id) else {
+                continue;
+            };
+            let due = match entry.last {
+                _ if entry.interval == 0 => false,
+                Some(last) => now.wrapping_sub(last) >= entry.interval,
+                None => true,
+            };
+            if !due {
+                continue;
+            }
+            let message = CANAerospaceMessage {
+                message_type: entry.message_type,
+                node_id: self.node_id,
+                service_code: ServiceCodeEnum::UNKNOWN,
+                message_code: 0,
+                data: entry.source.sample(),
+            };
+            self.send_message(message)?;
+            if let Some(entry) = self.scheduler.entries.get_mut(&id) {
+                entry.last = Some(now);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! # CANAerospace - CAN Baudrate Setting Service
+//!
+//! A [crate::types::ServiceCodeEnum::BSS] request carries the code of the new [Bitrate] in its `message_code`.
+//! The response carries [BSS_OK] or [BSS_INVALID] in its `message_code` and no data, it is still sent with the
+//! current bit rate. The driver is switched to the new bit rate after the response is sent, see
+//! [crate::driver::CANAerospaceDriver::set_bitrate], and the bit rate is stored by the
+//! [crate::config::ConfigStore] if one is set.
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    statistics::count,
+    types::{Bitrate, DataType},
+    CANAerospaceLite,
+};
+
+/// `message_code` of the response to an accepted BSS request
+pub const BSS_OK: u8 = 0;
+/// `message_code` of the response to a BSS request with an unknown code or a bit rate not supported by the driver
+pub const BSS_INVALID: u8 = -1i8 as u8;
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Accepts the requested bit rate if the driver supports it. Broadcasted requests are executed but not answered.
+    pub(crate) fn handle_bss(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let bitrate = Bitrate::from_code(request.message_code)
+            .filter(|&bitrate| self.driver.supports_bitrate(bitrate));
+        self.config.pending_bitrate = bitrate;
+        if request.node_id == 0 {
+            return None;
+        }
+        Some(ServiceResponse {
+            message_code: if bitrate.is_some() {
+                BSS_OK
+            } else {
+                BSS_INVALID
+            },
+            data: DataType::NODATA,
+        })
+    }
+
+    /// Switches the driver to the bit rate accepted by the last BSS request and stores it
+    pub(crate) fn apply_bitrate(&mut self, bitrate: Bitrate) -> Result<(), Error<D::Error>> {
+        self.driver.set_bitrate(bitrate).map_err(|e| {
+            count(&mut self.statistics.driver_errors);
+            Error::Driver(e)
+        })?;
+        if let Some(store) = self.config.store.as_deref_mut() {
+            store.store_bitrate(bitrate);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+//! # CANAerospace - CAN ID Setting Service
+//!
+//! The node keeps a remap table from logical identifiers, which are used by the application, to the
+//! identifiers which are actually sent and received on the bus. [CANAerospaceLite::send_message] sends
+//! logical identifiers on their actual ones and received frames are queued with their logical identifier, so
+//! identical units can share a bus with different identifiers. Service channels can not be remapped.
+//! Acceptance filters, see [crate::filter], apply to the actual identifiers.
+//!
+//! A [crate::types::ServiceCodeEnum::CSS] request carries the logical and the actual identifier as
+//! [DataType::USHORT2], equal identifiers remove the remap of the logical identifier. The response carries
+//! [CSS_OK], [CSS_INVALID] or [CSS_TABLE_FULL] in its `message_code` and no data. Accepted remaps are stored by
+//! the [crate::config::ConfigStore] if one is set. Broadcasted requests are ignored, they would remap all
+//! nodes alike.
+
+use heapless::LinearMap;
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, MessageType},
+    CANAerospaceLite,
+};
+
+/// Maximum number of remapped identifiers
+pub const REMAP_TABLE_SIZE: usize = 16;
+
+/// `message_code` of the response to an accepted CSS request
+pub const CSS_OK: u8 = 0;
+/// `message_code` of the response to a CSS request without [DataType::USHORT2], with service channels or with an
+/// actual identifier which is already used by another logical identifier
+pub const CSS_INVALID: u8 = -1i8 as u8;
+/// `message_code` of the response to a CSS request which does not fit into the remap table
+pub const CSS_TABLE_FULL: u8 = -2i8 as u8;
+
+/// Actual identifiers by logical identifier
+#[derive(Debug, Default)]
+pub(crate) struct RemapTable {
+    entries: LinearMap<u16, u16, REMAP_TABLE_SIZE>,
+}
+
+impl RemapTable {
+    /// Returns the identifier which is sent on the bus for a logical one
+    pub(crate) fn to_actual(&self, message_type: MessageType) -> MessageType {
+        match message_type {
+            MessageType::NSH(_) | MessageType::NSL(_) | MessageType::INVALID => message_type,
+            _ => match self.entries.get(&message_type.id()) {
+                Some(&actual) => MessageType::from(actual),
+                None => message_type,
+            },
+        }
+    }
+
+    /// Returns the logical identifier of one received on the bus
+    pub(crate) fn to_logical(&self, message_type: MessageType) -> MessageType {
+        match message_type {
+            MessageType::NSH(_) | MessageType::NSL(_) | MessageType::INVALID => message_type,
+            _ => {
+                let id = message_type.id();
+                match self.entries.iter().find(|(_, &actual)| actual == id) {
+                    Some((&logical, _)) => MessageType::from(logical),
+                    None => message_type,
+                }
+            }
+        }
+    }
+
+    /// Remaps a logical identifier, or removes its remap if both identifiers are equal
+    pub(crate) fn set<E>(&mut self, logical: u16, actual: u16) -> Result<(), Error<E>> {
+        let remappable = |id| {
+            !matches!(
+                MessageType::from(id),
+                MessageType::NSH(_) | MessageType::NSL(_) | MessageType::INVALID
+            )
+        };
+        let taken = self
+            .entries
+            .iter()
+            .any(|(&other, &used)| other != logical && used == actual);
+        if !remappable(logical) || !remappable(actual) || taken {
+            return Err(Error::InvalidIdentifier);
+        }
+        if logical == actual {
+            self.entries.remove(&logical);
+        } else {
+            self.entries
+                .insert(logical, actual)
+                .map_err(|_| Error::TableFull)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sends and receives the logical identifier `logical` on the bus as `actual`, equal identifiers remove the
+    /// remap. Returns [Error::InvalidIdentifier] for service channels and actual identifiers which are already
+    /// used by another logical identifier.
+    /// # Example
+    /// ```ignore
+    /// // second unit of the same sensor
+    /// can_aerospace.remap_identifier(MessageType::NOD(315), MessageType::NOD(316))?;
+    /// ```
+    pub fn remap_identifier(
+        &mut self,
+        logical: MessageType,
+        actual: MessageType,
+    ) -> Result<(), Error<D::Error>> {
+        self.remap.set(logical.id(), actual.id())
+    }
+
+    /// Returns the identifier which is sent on the bus for the logical identifier, according to the remap table
+    /// and the active identifier distribution, see [crate::service::dss]
+    pub fn actual_identifier(&self, logical: MessageType) -> MessageType {
+        let actual = self.remap.to_actual(logical);
+        if actual != logical {
+            return actual;
+        }
+        let active = self.identification.configuration;
+        self.distributions.to_distribution(active, logical)
+    }
+
+    /// Returns the logical identifier of one received on the bus
+    pub(crate) fn logical_identifier(&self, actual: MessageType) -> MessageType {
+        let logical = self.remap.to_logical(actual);
+        if logical != actual {
+            return logical;
+        }
+        let active = self.identification.configuration;
+        self.distributions.to_standard(active, actual)
+    }
+
+    /// Changes the remap table. Broadcasted requests are ignored.
+    pub(crate) fn handle_css(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        if request.node_id == 0 {
+            return None;
+        }
+        let message_code = match request.data {
+            DataType::USHORT2(logical, actual) => match self.remap.set::<D::Error>(logical, actual)
+            {
+                Ok(()) => {
+                    if let Some(store) = self.config.store.as_deref_mut() {
+                        store.store_remap(logical, actual);
+                    }
+                    CSS_OK
+                }
+                Err(Error::TableFull) => CSS_TABLE_FULL,
+                Err(_) => CSS_INVALID,
+            },
+            _ => CSS_INVALID,
+        };
+        Some(ServiceResponse {
+            message_code,
+            data: DataType::NODATA,
+        })
+    }
+}
@@ -0,0 +1,242 @@
+//! # CANAerospace - Data Download Service
+//!
+//! A [crate::types::ServiceCodeEnum::DDS] transfer is started by a request carrying the memory ID as [DataType::MEMID]
+//! and the number of data messages in its `message_code`. The node answers with [DDS_XON] when the
+//! [MemoryTarget] accepts the transfer. The data messages follow on the same service channel with a running
+//! `message_code` starting at 0, closed by the 32 bit sum of all data bytes as [DataType::CHKSUM] which
+//! is answered with [DDS_XOFF] on success.
+//!
+//! Whenever the target is busy the client is stopped with [DDS_XOFF] and resumed with [DDS_XON] by
+//! [CANAerospaceLite::poll_dds]. Sequence errors, checksum mismatches and timeouts abort the transfer with [DDS_ABORT].
+//!
+//! In the flash programming state transfers are written to the flash, see [crate::service::fps].
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, Timestamp},
+    CANAerospaceLite,
+};
+
+/// `message_code` of the response which stops the client, sent when the target is busy or the transfer is completed
+pub const DDS_XOFF: u8 = 0;
+/// `message_code` of the response which lets the client (continue to) send data messages
+pub const DDS_XON: u8 = 1;
+/// `message_code` of the response which aborts the transfer
+pub const DDS_ABORT: u8 = -1i8 as u8;
+
+/// Memory of the node which is written by [crate::types::ServiceCodeEnum::DDS] transfers
+pub trait MemoryTarget {
+    /// Prepares the memory `memid` to receive `blocks` data messages, returns false if it can not be written
+    fn begin(&mut self, memid: u32, blocks: u8) -> bool;
+
+    /// Writes the payload of data message `block`, returns false if the transfer has to be aborted
+    fn write(&mut self, memid: u32, block: u8, data: &[u8]) -> bool;
+
+    /// Returns false while the target can not accept further data messages
+    fn ready(&self) -> bool {
+        true
+    }
+
+    /// Called after the checksum is verified, returns false if the data can not be applied
+    fn commit(&mut self, memid: u32) -> bool;
+
+    /// Called when the transfer is aborted, partially written data shall be discarded
+    fn abort(&mut self, memid: u32);
+}
+
+/// Transfer in progress
+#[derive(Debug)]
+pub(crate) struct Transfer {
+    memid: u32,
+    blocks: u8,
+    received: u8,
+    checksum: u32,
+    /// Request which started the transfer, used to address responses from [CANAerospaceLite::poll_dds]
+    request: CANAerospaceMessage,
+    stopped: bool,
+    active: bool,
+    since: Option<Timestamp>,
+}
+
+/// State of the data download service
+#[derive(Default)]
+pub(crate) struct DataDownload<'a> {
+    pub(crate) target: Option<&'a mut dyn MemoryTarget>,
+    timeout: Timestamp,
+    transfer: Option<Transfer>,
+}
+
+impl core::fmt::Debug for DataDownload<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DataDownload")
+            .field("target", &self.target.is_some())
+            .field("timeout", &self.timeout)
+            .field("transfer", &self.transfer)
+            .finish()
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sets the memory which is written by [crate::types::ServiceCodeEnum::DDS] transfers.
+    /// DDS requests are answered as not supported until a target is set.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_memory_target(&mut configuration_table);
+    /// ```
+    pub fn set_memory_target(&mut self, target: &'a mut dyn MemoryTarget) {
+        if !self.flash.active {
+            self.abort_dds();
+        }
+        self.download.target = Some(target);
+    }
+
+    /// Sets the time without data messages after which a transfer is aborted, 0 disables the timeout.
+    /// The unit is the one of the time stamps passed to [CANAerospaceLite::poll_dds].
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_dds_timeout(500);
+    /// ```
+    pub fn set_dds_timeout(&mut self, timeout: Timestamp) {
+        self.download.timeout = timeout;
+    }
+
+    /// Returns true while a transfer is in progress
+    pub fn dds_in_progress(&self) -> bool {
+        self.download.transfer.is_some()
+    }
+
+    /// Resumes a stopped client as soon as the target is ready again and aborts the transfer on timeout.
+    /// Has to be called periodically while a transfer is in progress.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.poll_dds(millis())?;
+    /// ```
+    pub fn poll_dds(&mut self, now: Timestamp) -> Result<(), Error<D::Error>> {
+        let ready = match self.flash.memory_target() {
+            Some(target) => target.ready(),
+            None => match &self.download.target {
+                Some(target) => target.ready(),
+                None => return Ok(()),
+            },
+        };
+        let timeout = self.download.timeout;
+        let Some(transfer) = self.download.transfer.as_mut() else {
+            return Ok(());
+        };
+        let since = match transfer.since {
+            Some(since) if !transfer.active => since,
+            _ => {
+                transfer.active = false;
+                transfer.since = Some(now);
+                now
+            }
+        };
+        if timeout != 0 && now.wrapping_sub(since) >= timeout {
+            let request = transfer.request.clone();
+            self.abort_dds();
+            return self.respond(&request, dds_response(DDS_ABORT));
+        }
+        if transfer.stopped && ready {
+            transfer.stopped = false;
+            let request = transfer.request.clone();
+            return self.respond(&request, dds_response(DDS_XON));
+        }
+        Ok(())
+    }
+
+    /// Runs the receiver state machine. Broadcasted requests are ignored.
+    pub(crate) fn handle_dds(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        if request.node_id == 0 {
+            return None;
+        }
+        let target = match self.flash.memory_target() {
+            Some(target) => target,
+            None => self.download.target.as_deref_mut()?,
+        };
+        if let DataType::MEMID(memid) = request.data {
+            if let Some(transfer) = self.download.transfer.take() {
+                target.abort(transfer.memid);
+            }
+            let blocks = request.message_code;
+            if blocks == 0 || !target.begin(memid, blocks) {
+                return Some(dds_response(DDS_ABORT));
+            }
+            self.download.transfer = Some(Transfer {
+                memid,
+                blocks,
+                received: 0,
+                checksum: 0,
+                request: request.clone(),
+                stopped: false,
+                active: true,
+                since: None,
+            });
+            return Some(dds_response(DDS_XON));
+        }
+
+        let Some(transfer) = self.download.transfer.as_mut() else {
+            return Some(dds_response(DDS_ABORT));
+        };
+        transfer.active = true;
+        let accepted = match request.data {
+            DataType::CHKSUM(checksum) => {
+                transfer.received == transfer.blocks
+                    && checksum == transfer.checksum
+                    && target.commit(transfer.memid)
+            }
+            data => {
+                let bytes = data.to_be_bytes();
+                let bytes = &bytes[..data.len() as usize];
+                let accepted = transfer.received < transfer.blocks
+                    && request.message_code == transfer.received
+                    && target.write(transfer.memid, transfer.received, bytes);
+                if accepted {
+                    transfer.received += 1;
+                    transfer.checksum = bytes
+                        .iter()
+                        .fold(transfer.checksum, |sum, &b| sum.wrapping_add(b as u32));
+                    if !target.ready() {
+                        transfer.stopped = true;
+                        return Some(dds_response(DDS_XOFF));
+                    }
+                    return None;
+                }
+                false
+            }
+        };
+        let transfer = self.download.transfer.take()?;
+        if accepted {
+            Some(dds_response(DDS_XOFF))
+        } else {
+            target.abort(transfer.memid);
+            Some(dds_response(DDS_ABORT))
+        }
+    }
+
+    pub(crate) fn abort_dds(&mut self) {
+        let Some(transfer) = self.download.transfer.take() else {
+            return;
+        };
+        let target = match self.flash.memory_target() {
+            Some(target) => target,
+            None => match self.download.target.as_deref_mut() {
+                Some(target) => target,
+                None => return,
+            },
+        };
+        target.abort(transfer.memid);
+    }
+}
+
+fn dds_response(message_code: u8) -> ServiceResponse {
+    ServiceResponse {
+        message_code,
+        data: DataType::NODATA,
+    }
+}
@@ -0,0 +1,155 @@
+//! # CANAerospace - CAN ID Distribution Setting Service
+//!
+//! The application uses the identifiers of the standard distribution, see [crate::ids::standard] with the
+//! `ids-standard` feature. User-defined distributions are tables which give other identifiers to standard ones.
+//! The node sends and receives on the identifiers of the active distribution, identifiers which are not in its
+//! table are used as they are. The number of the active distribution is reported in the IDS response,
+//! [crate::IDS_CONF_STANDARD] for the standard one. Remaps of [crate::service::css] take precedence.
+//!
+//! A [crate::types::ServiceCodeEnum::DSS] request carries the number of the distribution which is activated in
+//! its `message_code`. The response carries [DSS_OK] or [DSS_INVALID] in its `message_code` and no data.
+//! Activated distributions are stored by the [crate::config::ConfigStore] if one is set.
+//! Broadcasted requests are executed but not answered, so all nodes can switch at once.
+
+use heapless::LinearMap;
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, IDSConfiguration, MessageType},
+    CANAerospaceLite, IDS_CONF_STANDARD,
+};
+
+/// Maximum number of user-defined distributions
+pub const DISTRIBUTION_TABLE_SIZE: usize = 4;
+
+/// `message_code` of the response to an accepted DSS request
+pub const DSS_OK: u8 = 0;
+/// `message_code` of the response to a DSS request for a distribution which is not registered
+pub const DSS_INVALID: u8 = -1i8 as u8;
+
+/// User-defined distributions as `(standard, distribution)` identifier pairs by number
+#[derive(Debug, Default)]
+pub(crate) struct IdDistributions<'a> {
+    tables: LinearMap<u8, &'a [(u16, u16)], DISTRIBUTION_TABLE_SIZE>,
+}
+
+impl<'a> IdDistributions<'a> {
+    pub(crate) fn contains(&self, configuration: IDSConfiguration) -> bool {
+        configuration == IDS_CONF_STANDARD || self.tables.contains_key(&configuration.0)
+    }
+
+    /// Returns the identifier of the distribution for a standard one
+    pub(crate) fn to_distribution(
+        &self,
+        active: IDSConfiguration,
+        message_type: MessageType,
+    ) -> MessageType {
+        self.translate(active, message_type, |&(standard, distribution)| {
+            (standard, distribution)
+        })
+    }
+
+    /// Returns the standard identifier for one of the distribution
+    pub(crate) fn to_standard(
+        &self,
+        active: IDSConfiguration,
+        message_type: MessageType,
+    ) -> MessageType {
+        self.translate(active, message_type, |&(standard, distribution)| {
+            (distribution, standard)
+        })
+    }
+
+    fn translate(
+        &self,
+        active: IDSConfiguration,
+        message_type: MessageType,
+        pair: impl Fn(&(u16, u16)) -> (u16, u16),
+    ) -> MessageType {
+        let table = match (message_type, self.tables.get(&active.0)) {
+            (MessageType::NSH(_) | MessageType::NSL(_) | MessageType::INVALID, _) | (_, None) => {
+                return message_type
+            }
+            (_, Some(table)) => table,
+        };
+        let id = message_type.id();
+        match table.iter().map(pair).find(|&(from, _)| from == id) {
+            Some((_, to)) => MessageType::from(to),
+            None => message_type,
+        }
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Registers a user-defined distribution, which can be activated by [CANAerospaceLite::set_ids_configuration]
+    /// and [crate::types::ServiceCodeEnum::DSS] requests, as `(standard, distribution)` identifier pairs.
+    /// Registering an existing number replaces the distribution.
+    ///
+    /// Returns [Error::InvalidDistribution] for the number of the standard distribution and
+    /// [Error::InvalidIdentifier] if the table contains service channels or identifiers which are not unique.
+    /// # Example
+    /// ```ignore
+    /// static TRAINER: [(u16, u16); 2] = [(300, 1200), (301, 1201)];
+    /// can_aerospace.register_id_distribution(IDSConfiguration(1), &TRAINER)?;
+    /// ```
+    pub fn register_id_distribution(
+        &mut self,
+        configuration: IDSConfiguration,
+        identifiers: &'a [(u16, u16)],
+    ) -> Result<(), Error<D::Error>> {
+        if configuration == IDS_CONF_STANDARD {
+            return Err(Error::InvalidDistribution);
+        }
+        let valid = |id| {
+            !matches!(
+                MessageType::from(id),
+                MessageType::NSH(_) | MessageType::NSL(_) | MessageType::INVALID
+            )
+        };
+        let unique = identifiers.iter().enumerate().all(|(i, &(standard, id))| {
+            identifiers[..i]
+                .iter()
+                .all(|&(other, other_id)| other != standard && other_id != id)
+        });
+        if !unique || !identifiers.iter().all(|&(a, b)| valid(a) && valid(b)) {
+            return Err(Error::InvalidIdentifier);
+        }
+        self.distributions
+            .tables
+            .insert(configuration.0, identifiers)
+            .map_err(|_| Error::TableFull)?;
+        Ok(())
+    }
+
+    /// Returns the number of the active distribution
+    pub fn ids_configuration(&self) -> IDSConfiguration {
+        self.identification.configuration
+    }
+
+    /// Activates a distribution. Broadcasted requests are executed but not answered.
+    pub(crate) fn handle_dss(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let configuration = IDSConfiguration(request.message_code);
+        let message_code = match self.set_ids_configuration(configuration) {
+            Ok(()) => {
+                if let Some(store) = self.config.store.as_deref_mut() {
+                    store.store_id_distribution(configuration);
+                }
+                DSS_OK
+            }
+            Err(_) => DSS_INVALID,
+        };
+        if request.node_id == 0 {
+            return None;
+        }
+        Some(ServiceResponse {
+            message_code,
+            data: DataType::NODATA,
+        })
+    }
+}
@@ -0,0 +1,337 @@
+//! # CANAerospace - Data Upload Service
+//!
+//! A [crate::types::ServiceCodeEnum::DUS] request carries the memory ID as [DataType::MEMID] and the number of
+//! data messages to upload in its `message_code`. The server answers with the data messages on the response channel,
+//! numbered by a running `message_code` starting at 0 and carrying 1 to 4 bytes as `UCHAR` types, followed by the
+//! 32 bit sum of all data bytes as [DataType::CHKSUM] with `message_code` set to the number of data messages.
+//! Requests which can not be served are answered with [DUS_ABORT] and [DataType::NODATA].
+//!
+//! The data messages are sent one at a time by [CANAerospaceLite::poll_dus], so a stream does not overrun the
+//! transmit mailboxes of the driver.
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, MessageType, ServiceChannel, ServiceCodeEnum},
+    CANAerospaceLite,
+};
+
+/// `message_code` of the response which aborts the upload
+pub const DUS_ABORT: u8 = -1i8 as u8;
+
+/// Memory of the node which is read by [crate::types::ServiceCodeEnum::DUS] requests
+pub trait MemorySource {
+    /// Prepares the memory `memid` to be read in `blocks` data messages, returns false if it can not be read
+    fn begin(&mut self, memid: u32, blocks: u8) -> bool;
+
+    /// Reads the data of message `block` into `data`, returns the number of valid bytes (1 to 4).
+    /// 0 aborts the upload.
+    fn read(&mut self, memid: u32, block: u8, data: &mut [u8; 4]) -> usize;
+}
+
+/// Reason of a failed upload requested by [CANAerospaceLite::request_upload]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UploadError {
+    /// The remote node aborted the upload
+    Aborted,
+    /// A message was received out of sequence or with a data type which the upload does not use
+    Sequence,
+    /// The received checksum does not match the data
+    Checksum,
+    /// The data does not fit into the buffer
+    BufferTooSmall,
+}
+
+/// State of the upload requested by [CANAerospaceLite::request_upload]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UploadStatus {
+    /// No upload was requested
+    Idle,
+    /// Waiting for data messages
+    InProgress,
+    /// All data messages are received and the checksum is valid, contains the number of bytes in the buffer
+    Complete(usize),
+    /// The upload failed
+    Failed(UploadError),
+}
+
+/// Data messages which are streamed to a client
+#[derive(Debug)]
+struct Stream {
+    memid: u32,
+    blocks: u8,
+    sent: u8,
+    checksum: u32,
+    /// Data of the current message, kept until it is sent to make retries possible
+    pending: Option<DataType>,
+    request: CANAerospaceMessage,
+}
+
+/// Upload requested from a remote node
+struct Upload<'a> {
+    node_id: u8,
+    response: MessageType,
+    blocks: u8,
+    received: u8,
+    len: usize,
+    checksum: u32,
+    buffer: &'a mut [u8],
+}
+
+/// State of the data upload service
+pub(crate) struct DataUpload<'a> {
+    pub(crate) source: Option<&'a mut dyn MemorySource>,
+    stream: Option<Stream>,
+    upload: Option<Upload<'a>>,
+    status: UploadStatus,
+}
+
+impl Default for DataUpload<'_> {
+    fn default() -> Self {
+        Self {
+            source: None,
+            stream: None,
+            upload: None,
+            status: UploadStatus::Idle,
+        }
+    }
+}
+
+impl core::fmt::Debug for DataUpload<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DataUpload")
+            .field("source", &self.source.is_some())
+            .field("stream", &self.stream)
+            .field("status", &self.status)
+            .finish()
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sets the memory which is read by [crate::types::ServiceCodeEnum::DUS] requests.
+    /// DUS requests are answered as not supported until a source is set.
+    /// The requested data messages are sent by [CANAerospaceLite::poll_dus].
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_memory_source(&mut calibration_table);
+    /// ```
+    pub fn set_memory_source(&mut self, source: &'a mut dyn MemorySource) {
+        self.upload.stream = None;
+        self.upload.source = Some(source);
+    }
+
+    /// Stops streaming data messages to the client
+    pub(crate) fn abort_dus(&mut self) {
+        self.upload.stream = None;
+    }
+
+    /// Sends the next data message of an upload requested by a client, at most one message per call.
+    /// Must be called periodically, e.g. from the transmit interrupt or the main loop. A message which could not
+    /// be sent because of a driver error is sent again by the next call.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.poll_dus()?;
+    /// ```
+    pub fn poll_dus(&mut self) -> Result<(), Error<D::Error>> {
+        let (Some(source), Some(stream)) =
+            (self.upload.source.as_mut(), self.upload.stream.as_mut())
+        else {
+            return Ok(());
+        };
+        let data = match stream.pending {
+            Some(data) => data,
+            None if stream.sent == stream.blocks => DataType::CHKSUM(stream.checksum),
+            None => {
+                let mut bytes = [0; 4];
+                let len = source.read(stream.memid, stream.sent, &mut bytes);
+                if len == 0 || len > bytes.len() {
+                    let request = stream.request.clone();
+                    self.upload.stream = None;
+                    return self.respond(&request, dus_abort());
+                }
+                stream.checksum = bytes[..len]
+                    .iter()
+                    .fold(stream.checksum, |sum, &b| sum.wrapping_add(b as u32));
+                match len {
+                    1 => DataType::UCHAR(bytes[0]),
+                    2 => DataType::UCHAR2(bytes[0], bytes[1]),
+                    3 => DataType::UCHAR3(bytes[0], bytes[1], bytes[2]),
+                    _ => DataType::UCHAR4(bytes[0], bytes[1], bytes[2], bytes[3]),
+                }
+            }
+        };
+        stream.pending = Some(data);
+        let request = stream.request.clone();
+        let response = ServiceResponse {
+            message_code: stream.sent,
+            data,
+        };
+        self.respond(&request, response)?;
+        if let Some(stream) = self.upload.stream.as_mut() {
+            if stream.sent == stream.blocks {
+                self.upload.stream = None;
+            } else {
+                stream.sent += 1;
+                stream.pending = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true while data messages of an upload requested by a client are left to be sent by
+    /// [CANAerospaceLite::poll_dus]
+    pub fn dus_streaming(&self) -> bool {
+        self.upload.stream.is_some()
+    }
+
+    /// Requests an upload of `blocks` data messages of memory `memid` from node `node_id`.
+    /// The request is sent on the service channel `channel`.
+    /// The received data is written to `buffer`, see [CANAerospaceLite::upload_status].
+    /// # Example
+    /// ```ignore
+    /// let channel = ServiceChannel::new(ChannelPriority::High, 0).unwrap();
+    /// can_aerospace.request_upload(0x20, channel, 0x10, 8, &mut buffer)?;
+    /// ```
+    pub fn request_upload(
+        &mut self,
+        node_id: u8,
+        channel: ServiceChannel,
+        memid: u32,
+        blocks: u8,
+        buffer: &'a mut [u8],
+    ) -> Result<(), Error<D::Error>> {
+        self.upload.upload = None;
+        self.send_message(CANAerospaceMessage {
+            message_type: channel.request_id(),
+            node_id,
+            service_code: ServiceCodeEnum::DUS,
+            message_code: blocks,
+            data: DataType::MEMID(memid),
+        })?;
+        self.upload.upload = Some(Upload {
+            node_id,
+            response: channel.response_id(),
+            blocks,
+            received: 0,
+            len: 0,
+            checksum: 0,
+            buffer,
+        });
+        self.upload.status = UploadStatus::InProgress;
+        Ok(())
+    }
+
+    /// Returns the state of the upload requested by [CANAerospaceLite::request_upload]
+    pub fn upload_status(&self) -> UploadStatus {
+        self.upload.status
+    }
+
+    /// Cancels the upload requested by [CANAerospaceLite::request_upload] and gives back the buffer
+    /// # Example
+    /// ```ignore
+    /// if let UploadStatus::Complete(len) = can_aerospace.upload_status() {
+    ///     let buffer = can_aerospace.release_upload().unwrap();
+    ///     apply_calibration(&buffer[..len]);
+    /// }
+    /// ```
+    pub fn release_upload(&mut self) -> Option<&'a mut [u8]> {
+        if self.upload.status == UploadStatus::InProgress {
+            self.upload.status = UploadStatus::Idle;
+        }
+        self.upload.upload.take().map(|upload| upload.buffer)
+    }
+
+    /// Starts streaming the requested data messages with [CANAerospaceLite::poll_dus]. Broadcasted requests are ignored.
+    pub(crate) fn handle_dus(
+        &mut self,
+        request: &CANAerospaceMessage,
+    ) -> Result<(), Error<D::Error>> {
+        let Some(source) = self.upload.source.as_mut() else {
+            return Ok(());
+        };
+        if request.node_id == 0 {
+            return Ok(());
+        }
+        let blocks = request.message_code;
+        let memid = match request.data {
+            DataType::MEMID(memid) if blocks != 0 && source.begin(memid, blocks) => memid,
+            _ => {
+                self.upload.stream = None;
+                return self.respond(request, dus_abort());
+            }
+        };
+        self.upload.stream = Some(Stream {
+            memid,
+            blocks,
+            sent: 0,
+            checksum: 0,
+            pending: None,
+            request: request.clone(),
+        });
+        Ok(())
+    }
+
+    /// Reassembles the data messages of the requested upload, returns false if the message does not belong to it
+    pub(crate) fn handle_upload_response(&mut self, response: &CANAerospaceMessage) -> bool {
+        let Some(upload) = self.upload.upload.as_mut() else {
+            return false;
+        };
+        if self.upload.status != UploadStatus::InProgress
+            || response.service_code != ServiceCodeEnum::DUS
+            || response.message_type != upload.response
+            || response.node_id != upload.node_id
+        {
+            return false;
+        }
+        let status = match response.data {
+            DataType::NODATA => UploadStatus::Failed(UploadError::Aborted),
+            _ if response.message_code != upload.received => {
+                UploadStatus::Failed(UploadError::Sequence)
+            }
+            DataType::CHKSUM(checksum) if upload.received == upload.blocks => {
+                if checksum == upload.checksum {
+                    UploadStatus::Complete(upload.len)
+                } else {
+                    UploadStatus::Failed(UploadError::Checksum)
+                }
+            }
+            _ if upload.received == upload.blocks => UploadStatus::Failed(UploadError::Sequence),
+            // the checksum arrived before the last data message
+            DataType::CHKSUM(_) => UploadStatus::Failed(UploadError::Sequence),
+            data @ (DataType::UCHAR(_)
+            | DataType::UCHAR2(..)
+            | DataType::UCHAR3(..)
+            | DataType::UCHAR4(..)) => {
+                let bytes = data.to_be_bytes();
+                let bytes = &bytes[..data.len() as usize];
+                match upload.buffer.get_mut(upload.len..upload.len + bytes.len()) {
+                    Some(chunk) => {
+                        chunk.copy_from_slice(bytes);
+                        upload.len += bytes.len();
+                        upload.received += 1;
+                        upload.checksum = bytes
+                            .iter()
+                            .fold(upload.checksum, |sum, &b| sum.wrapping_add(b as u32));
+                        UploadStatus::InProgress
+                    }
+                    None => UploadStatus::Failed(UploadError::BufferTooSmall),
+                }
+            }
+            _ => UploadStatus::Failed(UploadError::Sequence),
+        };
+        self.upload.status = status;
+        true
+    }
+}
+
+fn dus_abort() -> ServiceResponse {
+    ServiceResponse {
+        message_code: DUS_ABORT,
+        data: DataType::NODATA,
+    }
+}
@@ -0,0 +1,166 @@
+//! # CANAerospace - FLASH Programming Service
+//!
+//! A [crate::types::ServiceCodeEnum::FPS] request carries the security code of the node in its `message_code`.
+//! If it matches, the node enters the flash programming state and answers with [FPS_OK], otherwise with
+//! [FPS_INVALID_SECURITY_CODE]. Broadcasted requests are ignored.
+//!
+//! In the flash programming state [crate::types::ServiceCodeEnum::DDS] transfers are written to the
+//! [FlashProgrammer] instead of the [crate::service::dds::MemoryTarget]: the area given by the memory ID is
+//! erased when the transfer starts, programmed with every data message and verified after the checksum matched.
+
+use crate::{
+    driver::CANAerospaceDriver, message::CANAerospaceMessage, service::dds::MemoryTarget,
+    service::ServiceResponse, types::DataType, CANAerospaceLite,
+};
+
+/// `message_code` of the response to an FPS request with a valid security code
+pub const FPS_OK: u8 = 0;
+/// `message_code` of the response to an FPS request with an invalid security code
+pub const FPS_INVALID_SECURITY_CODE: u8 = -1i8 as u8;
+
+/// Flash memory of the node which is programmed in the flash programming state
+pub trait FlashProgrammer {
+    /// Erases the flash area `memid`, returns false if it can not be erased
+    fn erase(&mut self, memid: u32) -> bool;
+
+    /// Programs `data` at byte `offset` of the flash area `memid`, returns false on failure
+    fn program(&mut self, memid: u32, offset: u32, data: &[u8]) -> bool;
+
+    /// Verifies the first `len` programmed bytes of the flash area `memid`, returns false if they are corrupted
+    fn verify(&mut self, memid: u32, len: u32) -> bool;
+
+    /// Returns false while erasing or programming is in progress
+    fn ready(&self) -> bool {
+        true
+    }
+
+    /// Called when the node leaves the flash programming state, e.g. to start the new software
+    fn finish(&mut self) {}
+}
+
+/// Adapts a [FlashProgrammer] to receive DDS transfers
+struct FlashTarget<'a> {
+    programmer: &'a mut dyn FlashProgrammer,
+    offset: u32,
+}
+
+impl MemoryTarget for FlashTarget<'_> {
+    fn begin(&mut self, memid: u32, _blocks: u8) -> bool {
+        self.offset = 0;
+        self.programmer.erase(memid)
+    }
+
+    fn write(&mut self, memid: u32, _block: u8, data: &[u8]) -> bool {
+        let programmed = self.programmer.program(memid, self.offset, data);
+        self.offset += data.len() as u32;
+        programmed
+    }
+
+    fn ready(&self) -> bool {
+        self.programmer.ready()
+    }
+
+    fn commit(&mut self, memid: u32) -> bool {
+        self.programmer.verify(memid, self.offset)
+    }
+
+    fn abort(&mut self, _memid: u32) {}
+}
+
+/// State of the flash programming service
+#[derive(Default)]
+pub(crate) struct FlashProgramming<'a> {
+    target: Option<FlashTarget<'a>>,
+    security_code: u8,
+    pub(crate) active: bool,
+}
+
+impl<'a> FlashProgramming<'a> {
+    /// Returns the flash as DDS target while the node is in the flash programming state
+    pub(crate) fn memory_target(&mut self) -> Option<&mut dyn MemoryTarget> {
+        match self.target.as_mut() {
+            Some(target) if self.active => Some(target),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_supported(&self) -> bool {
+        self.target.is_some()
+    }
+}
+
+impl core::fmt::Debug for FlashProgramming<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlashProgramming")
+            .field("programmer", &self.target.is_some())
+            .field("active", &self.active)
+            .finish()
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sets the flash which is programmed after an [crate::types::ServiceCodeEnum::FPS] request with
+    /// `security_code`. FPS requests are answered as not supported until a programmer is set.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_flash_programmer(&mut flash, 0x5A);
+    /// ```
+    pub fn set_flash_programmer(
+        &mut self,
+        programmer: &'a mut dyn FlashProgrammer,
+        security_code: u8,
+    ) {
+        self.leave_flash_programming();
+        self.flash.target = Some(FlashTarget {
+            programmer,
+            offset: 0,
+        });
+        self.flash.security_code = security_code;
+    }
+
+    /// Returns true while the node is in the flash programming state
+    pub fn is_flash_programming(&self) -> bool {
+        self.flash.active
+    }
+
+    /// Leaves the flash programming state, a DDS transfer in progress is aborted
+    /// # Example
+    /// ```ignore
+    /// if can_aerospace.is_flash_programming() && !can_aerospace.dds_in_progress() {
+    ///     can_aerospace.leave_flash_programming();
+    /// }
+    /// ```
+    pub fn leave_flash_programming(&mut self) {
+        if !self.flash.active {
+            return;
+        }
+        self.abort_dds();
+        self.flash.active = false;
+        if let Some(target) = self.flash.target.as_mut() {
+            target.programmer.finish();
+        }
+    }
+
+    /// Enters the flash programming state if the security code matches. Broadcasted requests are ignored.
+    pub(crate) fn handle_fps(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        if request.node_id == 0 {
+            return None;
+        }
+        let message_code = if request.message_code == self.flash.security_code {
+            if !self.flash.active {
+                self.abort_dds();
+                self.flash.active = true;
+            }
+            FPS_OK
+        } else {
+            FPS_INVALID_SECURITY_CODE
+        };
+        Some(ServiceResponse {
+            message_code,
+            data: DataType::NODATA,
+        })
+    }
+}
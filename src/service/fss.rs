@@ -0,0 +1,70 @@
+//! # CANAerospace - Filter Setting Service
+//!
+//! [crate::types::ServiceCodeEnum::FSS] requests change the acceptance filters of the node, see [crate::filter].
+//! The `message_code` of a request selects the operation:
+//! * [FSS_ADD_RANGE] takes the first and last accepted identifier as [DataType::USHORT2]
+//! * [FSS_ADD_MASK] takes the identifier and the mask as [DataType::USHORT2]
+//! * [FSS_DROP_NODE] takes the node ID whose frames are dropped as [DataType::UCHAR]
+//! * [FSS_CLEAR] removes all filters and dropped nodes
+//!
+//! The response carries [FSS_OK], [FSS_INVALID] or [FSS_TABLE_FULL] in its `message_code` and no data.
+
+use crate::{
+    driver::CANAerospaceDriver,
+    filter::{AcceptanceFilter, FilterError},
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::DataType,
+    CANAerospaceLite,
+};
+
+/// `message_code` of a request which adds an [AcceptanceFilter::Range]
+pub const FSS_ADD_RANGE: u8 = 0;
+/// `message_code` of a request which adds an [AcceptanceFilter::Mask]
+pub const FSS_ADD_MASK: u8 = 1;
+/// `message_code` of a request which drops the frames of a node
+pub const FSS_DROP_NODE: u8 = 2;
+/// `message_code` of a request which removes all filters
+pub const FSS_CLEAR: u8 = 3;
+
+/// `message_code` of the response to an executed request
+pub const FSS_OK: u8 = 0;
+/// `message_code` of the response to a malformed request
+pub const FSS_INVALID: u8 = -1i8 as u8;
+/// `message_code` of the response to a request which does not fit into the filter table
+pub const FSS_TABLE_FULL: u8 = -2i8 as u8;
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Changes the acceptance filters. Broadcasted requests are executed but not answered.
+    pub(crate) fn handle_fss(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let result = match (request.message_code, request.data) {
+            (FSS_ADD_RANGE, DataType::USHORT2(first, last)) => {
+                self.filters.add(AcceptanceFilter::Range { first, last })
+            }
+            (FSS_ADD_MASK, DataType::USHORT2(id, mask)) => {
+                self.filters.add(AcceptanceFilter::Mask { id, mask })
+            }
+            (FSS_DROP_NODE, DataType::UCHAR(node_id)) => self.filters.drop_node(node_id),
+            (FSS_CLEAR, _) => {
+                self.filters.clear();
+                Ok(())
+            }
+            _ => Err(FilterError::Invalid),
+        };
+        if request.node_id == 0 {
+            return None;
+        }
+        let message_code = match result {
+            Ok(()) => FSS_OK,
+            Err(FilterError::Invalid) => FSS_INVALID,
+            Err(FilterError::TableFull) => FSS_TABLE_FULL,
+        };
+        Some(ServiceResponse {
+            message_code,
+            data: DataType::NODATA,
+        })
+    }
+}
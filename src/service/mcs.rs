@@ -0,0 +1,191 @@
+//! # CANAerospace - Module Configuration Service
+//!
+//! The node keeps typed configuration [Parameter]s by index. A [crate::types::ServiceCodeEnum::MCS] request
+//! carries the parameter index in its `message_code`. Requests without data read the parameter, requests with
+//! data of the parameter type write it. Writes are checked against the read-only flag and the limits of the
+//! parameter and the new value is stored by the [crate::config::ConfigStore] if one is set.
+//!
+//! The response echoes the index in its `message_code` and carries the current value of the parameter, or
+//! [DataType::ERROR] with one of the `MCS_*` error codes if the request is rejected.
+
+use heapless::LinearMap;
+
+use crate::{
+    driver::CANAerospaceDriver, error::Error, message::CANAerospaceMessage,
+    service::ServiceResponse, types::DataType, CANAerospaceLite,
+};
+
+/// Maximum number of parameters
+pub const PARAMETER_TABLE_SIZE: usize = 32;
+
+/// Error code of requests for a parameter which is not registered
+pub const MCS_UNKNOWN_PARAMETER: u32 = 1;
+/// Error code of writes to a read-only parameter
+pub const MCS_READ_ONLY: u32 = 2;
+/// Error code of writes with a value outside of the limits of the parameter
+pub const MCS_OUT_OF_RANGE: u32 = 3;
+/// Error code of writes with a value of a different type than the parameter
+pub const MCS_TYPE_MISMATCH: u32 = 4;
+
+/// Configuration parameter of the node
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameter {
+    /// Current value, its type is the type of the parameter
+    pub value: DataType,
+    /// Smallest accepted value of numeric types, compared per element for multi-element types
+    pub min: Option<DataType>,
+    /// Largest accepted value of numeric types, compared per element for multi-element types
+    pub max: Option<DataType>,
+    /// Read-only parameters can not be written by MCS requests
+    pub read_only: bool,
+}
+
+impl Parameter {
+    /// Returns the error code if `value` can not be assigned to the parameter
+    fn check(&self, value: DataType) -> Result<(), u32> {
+        if value.type_id() != self.value.type_id() {
+            return Err(MCS_TYPE_MISMATCH);
+        }
+        let below = |limit: Option<DataType>| match limit {
+            // NaN is not ordered, so it would pass every limit
+            Some(limit) => {
+                elementwise(limit, value, |limit, value| value.is_nan() || value < limit)
+            }
+            None => false,
+        };
+        let above = |limit: Option<DataType>| match limit {
+            Some(limit) => {
+                elementwise(limit, value, |limit, value| value.is_nan() || value > limit)
+            }
+            None => false,
+        };
+        if below(self.min) || above(self.max) {
+            return Err(MCS_OUT_OF_RANGE);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the numeric elements of the value, None for types without numeric meaning
+fn elements(data: DataType) -> Option<([f64; 4], usize)> {
+    let elements = match data {
+        DataType::FLOAT(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::LONG(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::ULONG(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::SHORT(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::USHORT(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::CHAR(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::UCHAR(a) => ([a.into(), 0.0, 0.0, 0.0], 1),
+        DataType::SHORT2(a, b) => ([a.into(), b.into(), 0.0, 0.0], 2),
+        DataType::USHORT2(a, b) => ([a.into(), b.into(), 0.0, 0.0], 2),
+        DataType::CHAR2(a, b) => ([a.into(), b.into(), 0.0, 0.0], 2),
+        DataType::UCHAR2(a, b) => ([a.into(), b.into(), 0.0, 0.0], 2),
+        DataType::CHAR3(a, b, c) => ([a.into(), b.into(), c.into(), 0.0], 3),
+        DataType::UCHAR3(a, b, c) => ([a.into(), b.into(), c.into(), 0.0], 3),
+        DataType::CHAR4(a, b, c, d) => ([a.into(), b.into(), c.into(), d.into()], 4),
+        DataType::UCHAR4(a, b, c, d) => ([a.into(), b.into(), c.into(), d.into()], 4),
+        _ => return None,
+    };
+    Some(elements)
+}
+
+/// Returns true if `violates` is true for any element of `value` and the same element of `limit`
+fn elementwise(limit: DataType, value: DataType, violates: impl Fn(f64, f64) -> bool) -> bool {
+    match (elements(limit), elements(value)) {
+        (Some((limit, len)), Some((value, _))) => limit[..len]
+            .iter()
+            .zip(&value[..len])
+            .any(|(&limit, &value)| violates(limit, value)),
+        _ => false,
+    }
+}
+
+/// Parameters of a node by index
+pub(crate) type ParameterTable = LinearMap<u8, Parameter, PARAMETER_TABLE_SIZE>;
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Registers a parameter which can be read and written by [crate::types::ServiceCodeEnum::MCS] requests
+    /// with `index`, registering an existing index replaces the parameter. Returns [Error::InvalidParameter]
+    /// if the limits are of a different type or the value is outside of them.
+    /// MCS requests are answered as not supported until a parameter is registered.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.register_parameter(3, Parameter {
+    ///     value: DataType::USHORT(100),
+    ///     min: Some(DataType::USHORT(10)),
+    ///     max: Some(DataType::USHORT(1000)),
+    ///     read_only: false,
+    /// })?;
+    /// ```
+    pub fn register_parameter(
+        &mut self,
+        index: u8,
+        parameter: Parameter,
+    ) -> Result<(), Error<D::Error>> {
+        let limits = [parameter.min, parameter.max];
+        let mismatch = limits
+            .iter()
+            .flatten()
+            .any(|limit| limit.type_id() != parameter.value.type_id());
+        if mismatch || parameter.check(parameter.value).is_err() {
+            return Err(Error::InvalidParameter);
+        }
+        self.parameters
+            .insert(index, parameter)
+            .map_err(|_| Error::TableFull)?;
+        Ok(())
+    }
+
+    /// Returns the current value of a parameter
+    pub fn parameter(&self, index: u8) -> Option<DataType> {
+        self.parameters.get(&index).map(|parameter| parameter.value)
+    }
+
+    /// Changes the value of a parameter, also if it is read-only. Returns [Error::InvalidParameter] if the
+    /// parameter is not registered or the value is of a different type or outside of the limits.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_parameter(3, DataType::USHORT(250))?;
+    /// ```
+    pub fn set_parameter(&mut self, index: u8, value: DataType) -> Result<(), Error<D::Error>> {
+        let parameter = self
+            .parameters
+            .get_mut(&index)
+            .ok_or(Error::InvalidParameter)?;
+        parameter
+            .check(value)
+            .map_err(|_| Error::InvalidParameter)?;
+        parameter.value = value;
+        Ok(())
+    }
+
+    /// Reads or writes a parameter. Broadcasted writes are executed but not answered.
+    pub(crate) fn handle_mcs(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let index = request.message_code;
+        let data = match self.parameters.get_mut(&index) {
+            None => DataType::ERROR(MCS_UNKNOWN_PARAMETER),
+            Some(parameter) if request.data == DataType::NODATA => parameter.value,
+            Some(parameter) if parameter.read_only => DataType::ERROR(MCS_READ_ONLY),
+            Some(parameter) => match parameter.check(request.data) {
+                Ok(()) => {
+                    parameter.value = request.data;
+                    if let Some(store) = self.config.store.as_deref_mut() {
+                        store.store_parameter(index, request.data);
+                    }
+                    request.data
+                }
+                Err(code) => DataType::ERROR(code),
+            },
+        };
+        if request.node_id == 0 {
+            return None;
+        }
+        Some(ServiceResponse {
+            message_code: index,
+            data,
+        })
+    }
+}
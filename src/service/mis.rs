@@ -0,0 +1,143 @@
+//! # CANAerospace - Module Information Service
+//!
+//! The node describes its internal modules, e.g. software partitions and sensors, by a table of [ModuleInfo].
+//! A [crate::types::ServiceCodeEnum::MIS] request carries the module index in its `message_code` and the
+//! requested item as [DataType::UCHAR], a request without data asks for [MIS_ITEM_TYPE].
+//!
+//! The response echoes the module index in its `message_code` and carries the item:
+//! - [MIS_ITEM_TYPE]: [ModuleKind] code and revision as [DataType::UCHAR2]
+//! - [MIS_ITEM_PART_NUMBER]: part number as [DataType::ULONG]
+//! - [MIS_ITEM_SERIAL_NUMBER]: serial number as [DataType::ULONG]
+//!
+//! Requests for unknown modules or items are answered with [MIS_INVALID] and no data.
+
+use heapless::Vec;
+
+use crate::{
+    driver::CANAerospaceDriver, error::Error, message::CANAerospaceMessage,
+    service::ServiceResponse, types::DataType, CANAerospaceLite,
+};
+
+/// Maximum number of modules of a node
+pub const MODULE_TABLE_SIZE: usize = 16;
+
+/// Item carrying the kind and revision of the module
+pub const MIS_ITEM_TYPE: u8 = 0;
+/// Item carrying the part number of the module
+pub const MIS_ITEM_PART_NUMBER: u8 = 1;
+/// Item carrying the serial number of the module
+pub const MIS_ITEM_SERIAL_NUMBER: u8 = 2;
+
+/// `message_code` of the response to a request for an unknown module or item
+pub const MIS_INVALID: u8 = -1i8 as u8;
+
+/// Kinds of modules
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModuleKind {
+    /// Software partition (code 0)
+    Software,
+    /// Hardware unit, e.g. a circuit board (code 1)
+    Hardware,
+    /// Sensor (code 2)
+    Sensor,
+    /// Actuator (code 3)
+    Actuator,
+    /// User-defined kind, codes from 100 are recommended
+    Custom(u8),
+}
+
+impl ModuleKind {
+    /// Returns the code which is sent in [MIS_ITEM_TYPE] responses
+    ///```
+    /// # use can_aerospace_lite::service::mis::ModuleKind;
+    /// assert_eq!(ModuleKind::Sensor.code(), 2);
+    /// assert_eq!(ModuleKind::Custom(120).code(), 120);
+    ///```
+    pub fn code(&self) -> u8 {
+        match self {
+            ModuleKind::Software => 0,
+            ModuleKind::Hardware => 1,
+            ModuleKind::Sensor => 2,
+            ModuleKind::Actuator => 3,
+            ModuleKind::Custom(code) => *code,
+        }
+    }
+}
+
+/// Description of a module of the node
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModuleInfo {
+    pub kind: ModuleKind,
+    pub revision: u8,
+    pub part_number: u32,
+    pub serial_number: u32,
+}
+
+impl ModuleInfo {
+    /// Returns the data of an item, None if the item is unknown
+    fn item(&self, item: u8) -> Option<DataType> {
+        match item {
+            MIS_ITEM_TYPE => Some(DataType::UCHAR2(self.kind.code(), self.revision)),
+            MIS_ITEM_PART_NUMBER => Some(DataType::ULONG(self.part_number)),
+            MIS_ITEM_SERIAL_NUMBER => Some(DataType::ULONG(self.serial_number)),
+            _ => None,
+        }
+    }
+}
+
+/// Module table of a node
+pub(crate) type ModuleTable = Vec<ModuleInfo, MODULE_TABLE_SIZE>;
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Adds a module to the module table and returns its index, which is used by
+    /// [crate::types::ServiceCodeEnum::MIS] requests. MIS requests are answered as not supported until
+    /// a module is registered.
+    /// # Example
+    /// ```ignore
+    /// let index = can_aerospace.register_module(ModuleInfo {
+    ///     kind: ModuleKind::Sensor,
+    ///     revision: 3,
+    ///     part_number: 4711,
+    ///     serial_number: 20210042,
+    /// })?;
+    /// ```
+    pub fn register_module(&mut self, module: ModuleInfo) -> Result<u8, Error<D::Error>> {
+        self.modules.push(module).map_err(|_| Error::TableFull)?;
+        Ok(self.modules.len() as u8 - 1)
+    }
+
+    /// Returns the module table, ordered by module index
+    pub fn modules(&self) -> &[ModuleInfo] {
+        &self.modules
+    }
+
+    /// Answers the requested item of a module. Broadcasted requests are not answered.
+    pub(crate) fn handle_mis(&self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        if request.node_id == 0 {
+            return None;
+        }
+        let item = match request.data {
+            DataType::NODATA => Some(MIS_ITEM_TYPE),
+            DataType::UCHAR(item) => Some(item),
+            _ => None,
+        };
+        let data = self
+            .modules
+            .get(request.message_code as usize)
+            .zip(item)
+            .and_then(|(module, item)| module.item(item));
+        Some(match data {
+            Some(data) => ServiceResponse {
+                message_code: request.message_code,
+                data,
+            },
+            None => ServiceResponse {
+                message_code: MIS_INVALID,
+                data: DataType::NODATA,
+            },
+        })
+    }
+}
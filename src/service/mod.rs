@@ -0,0 +1,108 @@
+//! # CANAerospace - Service
+//!
+//! Node services which answer requests received on [crate::types::MessageType::NSH] and
+//! [crate::types::MessageType::NSL] channels.
+//!
+//! Application specific services are implemented with [ServiceHandler] and registered to
+//! [crate::CANAerospaceLite] per [ServiceCodeEnum].
+
+use core::fmt;
+
+pub mod bss;
+pub mod css;
+pub mod dds;
+pub mod dss;
+pub mod dus;
+pub mod fps;
+pub mod fss;
+pub mod mcs;
+pub mod mis;
+pub mod nis;
+pub mod nss;
+pub mod scs;
+pub mod sts;
+pub mod tcs;
+pub mod tis;
+
+use heapless::LinearMap;
+
+use crate::{
+    message::CANAerospaceMessage,
+    types::{DataType, MessageCode, ServiceCode, ServiceCodeEnum},
+};
+
+/// Number of service codes which can have a registered [ServiceHandler]
+pub const SERVICE_HANDLER_TABLE_SIZE: usize = 8;
+
+/// `message_code` of the response which is sent for service codes without a handler.
+/// It differs from the -1 and -2 codes of the services, so a client can tell unsupported from invalid requests.
+pub const SERVICE_NOT_SUPPORTED: MessageCode = i8::MIN as MessageCode;
+
+/// Response of a service request which is sent on the response channel of the request
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ServiceResponse {
+    /// `message_code` of the response, its meaning is defined by the service
+    pub message_code: MessageCode,
+    /// Payload of the response
+    pub data: DataType,
+}
+
+/// Handles requests of a single service code
+///
+/// Implemented for closures, so a closure can be registered as a handler as well.
+pub trait ServiceHandler {
+    /// Handles a request addressed to this node or broadcasted to all nodes.
+    /// Returns None if no response must be sent.
+    fn handle(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse>;
+}
+
+impl<F> ServiceHandler for F
+where
+    F: FnMut(&CANAerospaceMessage) -> Option<ServiceResponse>,
+{
+    fn handle(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        self(request)
+    }
+}
+
+/// Registered service handlers by service code
+#[derive(Default)]
+pub(crate) struct ServiceRegistry<'a> {
+    handlers: LinearMap<ServiceCode, &'a mut dyn ServiceHandler, SERVICE_HANDLER_TABLE_SIZE>,
+}
+
+impl<'a> ServiceRegistry<'a> {
+    /// Registers the handler, a previously registered handler of the same code is replaced.
+    /// Returns the handler back if the table is full.
+    pub(crate) fn register(
+        &mut self,
+        code: ServiceCodeEnum,
+        handler: &'a mut dyn ServiceHandler,
+    ) -> Result<(), &'a mut dyn ServiceHandler> {
+        self.handlers
+            .insert(code.as_u8(), handler)
+            .map(|_| ())
+            .map_err(|(_, handler)| handler)
+    }
+
+    /// Removes and returns the handler of the code
+    pub(crate) fn unregister(
+        &mut self,
+        code: ServiceCodeEnum,
+    ) -> Option<&'a mut dyn ServiceHandler> {
+        self.handlers.remove(&code.as_u8())
+    }
+
+    /// Returns the handler of the code
+    pub(crate) fn get(&mut self, code: ServiceCodeEnum) -> Option<&mut (dyn ServiceHandler + 'a)> {
+        self.handlers
+            .get_mut(&code.as_u8())
+            .map(|handler| &mut **handler)
+    }
+}
+
+impl fmt::Debug for ServiceRegistry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.handlers.keys()).finish()
+    }
+}
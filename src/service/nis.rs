@@ -0,0 +1,49 @@
+//! # CANAerospace - NodeId Setting Service
+//!
+//! A [crate::types::ServiceCodeEnum::NIS] request carries the new node ID as [DataType::UCHAR].
+//! The response carries [NIS_OK] or [NIS_INVALID] in its `message_code` and no data. It is still sent
+//! with the current node ID, the node switches to the new one right after it, see
+//! [CANAerospaceLite::set_node_id], and the node ID is stored by the [crate::config::ConfigStore] if one is set.
+//! Broadcasted requests are ignored, they would give all nodes the same ID.
+
+use crate::{
+    driver::CANAerospaceDriver, message::CANAerospaceMessage, service::ServiceResponse,
+    types::DataType, CANAerospaceLite,
+};
+
+/// `message_code` of the response to an accepted NIS request
+pub const NIS_OK: u8 = 0;
+/// `message_code` of the response to a NIS request without [DataType::UCHAR] or with node ID 0
+pub const NIS_INVALID: u8 = -1i8 as u8;
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Accepts the requested node ID. Broadcasted requests are ignored.
+    pub(crate) fn handle_nis(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        if request.node_id == 0 {
+            return None;
+        }
+        let message_code = match request.data {
+            DataType::UCHAR(node_id) if node_id != 0 => {
+                self.config.pending_node_id = Some(node_id);
+                NIS_OK
+            }
+            _ => NIS_INVALID,
+        };
+        Some(ServiceResponse {
+            message_code,
+            data: DataType::NODATA,
+        })
+    }
+
+    /// Switches to the node ID accepted by the last NIS request and stores it
+    pub(crate) fn apply_node_id(&mut self, node_id: u8) {
+        if self.set_node_id(node_id).is_ok() {
+            if let Some(store) = self.config.store.as_deref_mut() {
+                store.store_node_id(node_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! # CANAerospace - Node Synchronisation Service
+//!
+//! [crate::types::ServiceCodeEnum::NSS] requests carry the time of the synchronisation master as [DataType::ULONG].
+//! The node keeps the offset between the master time and its local [TimeBase].
+
+use crate::{
+    driver::CANAerospaceDriver,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, Timestamp},
+    CANAerospaceLite,
+};
+
+/// `message_code` of the response to an addressed NSS request which is accepted
+pub const NSS_OK: u8 = 0;
+/// `message_code` of the response to an addressed NSS request without a [DataType::ULONG] time stamp
+pub const NSS_INVALID_TIMESTAMP: u8 = -1i8 as u8;
+
+/// Local clock of the node which is synchronised by [crate::types::ServiceCodeEnum::NSS] requests
+pub trait TimeBase {
+    /// Returns the current local time in the unit which is used by the synchronisation master
+    fn now(&self) -> Timestamp;
+}
+
+/// State of the node synchronisation
+#[derive(Default)]
+pub(crate) struct NodeSynchronisation<'a> {
+    pub(crate) time_base: Option<&'a dyn TimeBase>,
+    pub(crate) offset: Option<i32>,
+}
+
+impl core::fmt::Debug for NodeSynchronisation<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NodeSynchronisation")
+            .field("time_base", &self.time_base.is_some())
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Sets the local clock which is synchronised by [crate::types::ServiceCodeEnum::NSS] requests.
+    /// NSS requests are answered as not supported until a time base is set.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_time_base(&clock);
+    /// ```
+    pub fn set_time_base(&mut self, time_base: &'a dyn TimeBase) {
+        self.synchronisation.time_base = Some(time_base);
+        self.synchronisation.offset = None;
+    }
+
+    /// Returns master time minus local time of the last accepted NSS request,
+    /// None if the node is not synchronised yet.
+    pub fn clock_offset(&self) -> Option<i32> {
+        self.synchronisation.offset
+    }
+
+    /// Returns the current local time corrected by the clock offset,
+    /// None if there is no time base or the node is not synchronised yet.
+    /// # Example
+    /// ```ignore
+    /// if let Some(time) = can_aerospace.synchronised_time() {
+    ///     // timestamp sensor data
+    /// }
+    /// ```
+    pub fn synchronised_time(&self) -> Option<Timestamp> {
+        let time_base = self.synchronisation.time_base?;
+        let offset = self.synchronisation.offset?;
+        Some(time_base.now().wrapping_add(offset as u32))
+    }
+
+    /// Updates the clock offset. Broadcasted requests are not answered.
+    pub(crate) fn handle_nss(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let time_base = self.synchronisation.time_base?;
+        let message_code = match request.data {
+            DataType::ULONG(master) => {
+                self.synchronisation.offset = Some(master.wrapping_sub(time_base.now()) as i32);
+                NSS_OK
+            }
+            _ => NSS_INVALID_TIMESTAMP,
+        };
+        if request.node_id == 0 {
+            return None;
+        }
+        Some(ServiceResponse {
+            message_code,
+            data: DataType::NODATA,
+        })
+    }
+}
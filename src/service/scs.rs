@@ -0,0 +1,168 @@
+//! # CANAerospace - Simulation Control Service
+//!
+//! [crate::types::ServiceCodeEnum::SCS] requests switch output identifiers of the node into simulation mode.
+//! While an identifier is simulated, [CANAerospaceLite::send_message] transmits the injected value instead of the
+//! value given by the application. Nothing is transmitted on a simulated identifier until a value is injected.
+//!
+//! The `message_code` of a request selects the operation:
+//! * [SCS_SIMULATION_OFF], [SCS_SIMULATION_ON] and [SCS_QUERY] take the identifier as [DataType::USHORT] and select it
+//! * [SCS_INJECT] takes the simulated value of the selected identifier
+//!
+//! The response carries the simulation mode of the identifier in its `message_code`, or [SCS_INVALID] if the
+//! request can not be executed.
+
+use heapless::LinearMap;
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, MessageType},
+    CANAerospaceLite,
+};
+
+/// Maximum number of identifiers in simulation mode
+pub const SIMULATION_TABLE_SIZE: usize = 16;
+
+/// `message_code` of a request which switches an identifier back to normal operation
+pub const SCS_SIMULATION_OFF: u8 = 0;
+/// `message_code` of a request which switches an identifier into simulation mode
+pub const SCS_SIMULATION_ON: u8 = 1;
+/// `message_code` of a request which queries the simulation mode of an identifier
+pub const SCS_QUERY: u8 = 2;
+/// `message_code` of a request which injects the value of the selected identifier
+pub const SCS_INJECT: u8 = 3;
+/// `message_code` of the response to a request which can not be executed
+pub const SCS_INVALID: u8 = -1i8 as u8;
+
+/// State of the simulation control
+#[derive(Debug, Default)]
+pub(crate) struct Simulation {
+    pub(crate) enabled: bool,
+    /// Injected values of the simulated identifiers
+    values: LinearMap<u16, Option<DataType>, SIMULATION_TABLE_SIZE>,
+    selected: Option<u16>,
+}
+
+impl Simulation {
+    /// Returns the data to transmit on the identifier, None if the transmission has to be suppressed
+    pub(crate) fn apply(&self, message_type: MessageType, data: DataType) -> Option<DataType> {
+        match message_type.output_id().and_then(|id| self.values.get(&id)) {
+            Some(value) => *value,
+            None => Some(data),
+        }
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Enables [crate::types::ServiceCodeEnum::SCS] requests. They are answered as not supported by default.
+    /// Disabling switches all identifiers back to normal operation.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_simulation_control(true);
+    /// ```
+    pub fn set_simulation_control(&mut self, enabled: bool) {
+        self.simulation.enabled = enabled;
+        if !enabled {
+            self.simulation.values.clear();
+            self.simulation.selected = None;
+        }
+    }
+
+    /// Switches an output identifier into or out of simulation mode.
+    /// Only NOD, UDH and UDL identifiers can be simulated.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.set_simulation(MessageType::NOD(315), true)?;
+    /// can_aerospace.inject_simulated_value(MessageType::NOD(315), DataType::FLOAT(1013.25))?;
+    /// ```
+    pub fn set_simulation(
+        &mut self,
+        message_type: MessageType,
+        enabled: bool,
+    ) -> Result<(), Error<D::Error>> {
+        let id = message_type.output_id().ok_or(Error::InvalidIdentifier)?;
+        if !enabled {
+            self.simulation.values.remove(&id);
+        } else if !self.simulation.values.contains_key(&id) {
+            self.simulation
+                .values
+                .insert(id, None)
+                .map_err(|_| Error::TableFull)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the value which is transmitted on a simulated identifier
+    pub fn inject_simulated_value(
+        &mut self,
+        message_type: MessageType,
+        data: DataType,
+    ) -> Result<(), Error<D::Error>> {
+        let id = message_type.output_id().ok_or(Error::InvalidIdentifier)?;
+        let value = self
+            .simulation
+            .values
+            .get_mut(&id)
+            .ok_or(Error::InvalidIdentifier)?;
+        *value = Some(data);
+        Ok(())
+    }
+
+    /// Returns true if the identifier is in simulation mode
+    pub fn is_simulated(&self, message_type: MessageType) -> bool {
+        message_type
+            .output_id()
+            .is_some_and(|id| self.simulation.values.contains_key(&id))
+    }
+
+    /// Returns the value which is transmitted on a simulated identifier, None if no value is injected yet
+    pub fn simulated_value(&self, message_type: MessageType) -> Option<DataType> {
+        let id = message_type.output_id()?;
+        self.simulation.values.get(&id).copied().flatten()
+    }
+
+    /// Executes the request. Broadcasted requests are executed but not answered.
+    pub(crate) fn handle_scs(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let message_code = match (request.message_code, request.data) {
+            (SCS_INJECT, data) => match self.simulation.selected {
+                Some(id) => match self.inject_simulated_value(MessageType::from(id), data) {
+                    Ok(()) => SCS_SIMULATION_ON,
+                    Err(_) => SCS_INVALID,
+                },
+                None => SCS_INVALID,
+            },
+            (code @ (SCS_SIMULATION_OFF | SCS_SIMULATION_ON | SCS_QUERY), DataType::USHORT(id)) => {
+                let message_type = MessageType::from(id);
+                let result = match code {
+                    SCS_QUERY if message_type.output_id().is_some() => Ok(()),
+                    SCS_QUERY => Err(Error::InvalidIdentifier),
+                    _ => self.set_simulation(message_type, code == SCS_SIMULATION_ON),
+                };
+                match result {
+                    Ok(()) => {
+                        self.simulation.selected = Some(id);
+                        if self.is_simulated(message_type) {
+                            SCS_SIMULATION_ON
+                        } else {
+                            SCS_SIMULATION_OFF
+                        }
+                    }
+                    Err(_) => SCS_INVALID,
+                }
+            }
+            _ => SCS_INVALID,
+        };
+        if request.node_id == 0 {
+            return None;
+        }
+        Some(ServiceResponse {
+            message_code,
+            data: request.data,
+        })
+    }
+}
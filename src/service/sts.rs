@@ -0,0 +1,194 @@
+//! # CANAerospace - State Transmission Service
+//!
+//! A [crate::types::ServiceCodeEnum::STS] request makes the node transmit the current value of every identifier
+//! it publishes: the enabled identifiers of the cyclic scheduler, sampled from their
+//! [crate::scheduler::DataSource], and the identifiers of the state table with the value they were last sent with.
+//! The values are sent one by one by [CANAerospaceLite::poll_sts], followed by the response which carries the
+//! number of transmitted messages in its `message_code`.
+
+use heapless::{Deque, LinearMap};
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    scheduler::SCHEDULE_TABLE_SIZE,
+    service::ServiceResponse,
+    types::{DataType, MessageType, ServiceCodeEnum},
+    CANAerospaceLite,
+};
+
+/// Number of identifiers whose last value can be kept for STS requests
+pub const STATE_TABLE_SIZE: usize = 32;
+
+/// Number of identifiers which can be published at most
+const DUMP_SIZE: usize = SCHEDULE_TABLE_SIZE + STATE_TABLE_SIZE;
+
+/// Last transmitted values of the identifiers registered by [CANAerospaceLite::register_state_value]
+#[derive(Debug, Default)]
+pub(crate) struct StateTable {
+    values: LinearMap<u16, Option<DataType>, STATE_TABLE_SIZE>,
+    dump: Option<Dump>,
+}
+
+/// Identifiers whose values are left to be sent by [CANAerospaceLite::poll_sts]
+#[derive(Debug)]
+struct Dump {
+    ids: Deque<u16, DUMP_SIZE>,
+    /// Number of messages which were handed to the driver
+    sent: usize,
+    /// Request which is answered when all values are sent, None if no response is sent
+    request: Option<CANAerospaceMessage>,
+}
+
+impl StateTable {
+    /// Remembers the value if the identifier is registered
+    pub(crate) fn update(&mut self, message_type: MessageType, data: DataType) {
+        if let Some(value) = message_type
+            .output_id()
+            .and_then(|id| self.values.get_mut(&id))
+        {
+            *value = Some(data);
+        }
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Keeps the last value sent on `message_type` by [CANAerospaceLite::send_message] to transmit it again on
+    /// [crate::types::ServiceCodeEnum::STS] requests. Only NOD, UDH and UDL identifiers can be registered.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.register_state_value(MessageType::NOD(320))?;
+    /// ```
+    pub fn register_state_value(
+        &mut self,
+        message_type: MessageType,
+    ) -> Result<(), Error<D::Error>> {
+        let id = message_type.output_id().ok_or(Error::InvalidIdentifier)?;
+        if !self.state.values.contains_key(&id) {
+            self.state
+                .values
+                .insert(id, None)
+                .map_err(|_| Error::TableFull)?;
+        }
+        Ok(())
+    }
+
+    /// Starts transmitting the current value of all published identifiers, which are sent by
+    /// [CANAerospaceLite::poll_sts]. A running transmission is restarted.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.transmit_state();
+    /// ```
+    pub fn transmit_state(&mut self) {
+        self.start_dump(None);
+    }
+
+    /// Sends the next value of the state transmission, at most one message per call. The response to the
+    /// [crate::types::ServiceCodeEnum::STS] request is sent by the call after the last value.
+    /// Must be called periodically, e.g. from the transmit interrupt or the main loop. A message which could not
+    /// be sent because of a driver error is sent again by the next call.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.poll_sts()?;
+    /// ```
+    pub fn poll_sts(&mut self) -> Result<(), Error<D::Error>> {
+        loop {
+            let Some(dump) = self.state.dump.as_ref() else {
+                return Ok(());
+            };
+            let Some(&id) = dump.ids.front() else {
+                if let Some(request) = dump.request.clone() {
+                    let response = ServiceResponse {
+                        message_code: dump.sent.min(u8::MAX as usize) as u8,
+                        data: DataType::NODATA,
+                    };
+                    self.respond(&request, response)?;
+                }
+                self.state.dump = None;
+                return Ok(());
+            };
+            // identifiers which are not published any more are skipped
+            let Some((message_type, data)) = self.state_value(id) else {
+                self.next_state_value(false);
+                continue;
+            };
+            let sent = self.forward_message(CANAerospaceMessage {
+                message_type,
+                node_id: self.node_id,
+                service_code: ServiceCodeEnum::UNKNOWN,
+                message_code: 0,
+                data,
+            })?;
+            self.next_state_value(sent);
+            return Ok(());
+        }
+    }
+
+    /// Returns true while values of a state transmission or the response to the
+    /// [crate::types::ServiceCodeEnum::STS] request are left to be sent by [CANAerospaceLite::poll_sts]
+    pub fn sts_transmitting(&self) -> bool {
+        self.state.dump.is_some()
+    }
+
+    /// Stops the state transmission
+    pub(crate) fn abort_sts(&mut self) {
+        self.state.dump = None;
+    }
+
+    fn start_dump(&mut self, request: Option<CANAerospaceMessage>) {
+        let mut ids = Deque::new();
+        for (id, entry) in self.scheduler.entries.iter() {
+            if entry.interval != 0 {
+                ids.push_back(*id).ok();
+            }
+        }
+        for (id, value) in self.state.values.iter() {
+            if value.is_some() && !self.scheduler.entries.contains_key(id) {
+                ids.push_back(*id).ok();
+            }
+        }
+        self.state.dump = Some(Dump {
+            ids,
+            sent: 0,
+            request,
+        });
+    }
+
+    /// Returns the identifier and current value of `id`, None if it is not published any more
+    fn state_value(&mut self, id: u16) -> Option<(MessageType, DataType)> {
+        if let Some(entry) = self
+            .scheduler
+            .entries
+            .get_mut(&id)
+            .filter(|entry| entry.interval != 0)
+        {
+            return Some((entry.message_type, entry.source.sample()));
+        }
+        let data = (*self.state.values.get(&id)?)?;
+        Some((MessageType::from(id), data))
+    }
+
+    /// Moves on to the next value, a transmission without response ends with its last value
+    fn next_state_value(&mut self, sent: bool) {
+        let Some(dump) = self.state.dump.as_mut() else {
+            return;
+        };
+        dump.ids.pop_front();
+        if sent {
+            dump.sent += 1;
+        }
+        if dump.ids.is_empty() && dump.request.is_none() {
+            self.state.dump = None;
+        }
+    }
+
+    /// Starts the state transmission. Broadcasted requests are executed but not answered.
+    pub(crate) fn handle_sts(&mut self, request: &CANAerospaceMessage) {
+        let request = Some(request.clone()).filter(|request| request.node_id != 0);
+        self.start_dump(request);
+    }
+}
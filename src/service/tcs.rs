@@ -0,0 +1,191 @@
+//! # CANAerospace - Test Control Service
+//!
+//! A [crate::types::ServiceCodeEnum::TCS] request runs the built-in test whose ID is given in its `message_code`.
+//! The response carries [TCS_PASSED], [TCS_FAILED] or [TCS_UNKNOWN_TEST] in its `message_code` and the code
+//! returned by the test as [DataType::BLONG].
+//!
+//! The results can also be published periodically as a status word, see [CANAerospaceLite::publish_built_in_tests].
+
+use heapless::LinearMap;
+
+use crate::{
+    driver::CANAerospaceDriver,
+    error::Error,
+    message::CANAerospaceMessage,
+    service::ServiceResponse,
+    types::{DataType, MessageType, ServiceCodeEnum, Timestamp},
+    CANAerospaceLite,
+};
+
+/// Maximum number of built-in tests
+pub const TEST_TABLE_SIZE: usize = 8;
+
+/// `message_code` of the response to a test which passed
+pub const TCS_PASSED: u8 = 0;
+/// `message_code` of the response to a test which failed
+pub const TCS_FAILED: u8 = 1;
+/// `message_code` of the response to a request for a test which is not registered
+pub const TCS_UNKNOWN_TEST: u8 = -1i8 as u8;
+
+/// Built-in test of the node
+pub trait BuiltInTest {
+    /// Runs the test and returns 0 if it passed, otherwise a failure code
+    fn run(&mut self) -> u32;
+}
+
+impl<F> BuiltInTest for F
+where
+    F: FnMut() -> u32,
+{
+    fn run(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// Registered built-in tests and the publication of their results
+#[derive(Default)]
+pub(crate) struct BuiltInTests<'a> {
+    tests: LinearMap<u8, &'a mut dyn BuiltInTest, TEST_TABLE_SIZE>,
+    results: LinearMap<u8, u32, TEST_TABLE_SIZE>,
+    status: Option<MessageType>,
+    interval: Timestamp,
+    last: Option<Timestamp>,
+}
+
+impl<'a> BuiltInTests<'a> {
+    fn run(&mut self, test_id: u8) -> Option<u32> {
+        let code = self.tests.get_mut(&test_id)?.run();
+        // a slot is available for every registered test
+        let _ = self.results.insert(test_id, code);
+        Some(code)
+    }
+
+    /// Returns the status word, bit `n` is set if the test with ID `n` failed
+    fn status_word(&self) -> u32 {
+        self.results
+            .iter()
+            .filter(|(&id, &code)| id < 32 && code != 0)
+            .fold(0, |word, (&id, _)| word | 1 << id)
+    }
+}
+
+impl core::fmt::Debug for BuiltInTests<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BuiltInTests")
+            .field("results", &self.results)
+            .field("status", &self.status)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Registers a built-in test which can be run remotely by [crate::types::ServiceCodeEnum::TCS] requests
+    /// with `test_id`. Registering an existing ID replaces the test.
+    /// # Example
+    /// ```ignore
+    /// let mut ram_test = || if ram_ok() { 0 } else { 0x0BAD };
+    /// can_aerospace.register_built_in_test(1, &mut ram_test)?;
+    /// ```
+    pub fn register_built_in_test(
+        &mut self,
+        test_id: u8,
+        test: &'a mut dyn BuiltInTest,
+    ) -> Result<(), Error<D::Error>> {
+        self.tests
+            .tests
+            .insert(test_id, test)
+            .map_err(|_| Error::TableFull)?;
+        self.tests.results.remove(&test_id);
+        Ok(())
+    }
+
+    /// Runs a built-in test and returns its code, None if the test is not registered
+    pub fn run_built_in_test(&mut self, test_id: u8) -> Option<u32> {
+        self.tests.run(test_id)
+    }
+
+    /// Returns the code of the last run of a built-in test, None if it did not run yet
+    pub fn built_in_test_result(&self, test_id: u8) -> Option<u32> {
+        self.tests.results.get(&test_id).copied()
+    }
+
+    /// Runs all built-in tests every `interval` in [CANAerospaceLite::poll_built_in_tests] and publishes the
+    /// results as [DataType::BLONG] status word on `message_type`, which must be a [MessageType::NOD] identifier.
+    /// Bit `n` of the status word is set if the test with ID `n` failed. An interval of 0 disables the publication.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.publish_built_in_tests(MessageType::NOD(1700), 1000)?;
+    /// ```
+    pub fn publish_built_in_tests(
+        &mut self,
+        message_type: MessageType,
+        interval: Timestamp,
+    ) -> Result<(), Error<D::Error>> {
+        if !matches!(message_type, MessageType::NOD(_)) {
+            return Err(Error::InvalidIdentifier);
+        }
+        self.tests.status = Some(message_type);
+        self.tests.interval = interval;
+        self.tests.last = None;
+        Ok(())
+    }
+
+    /// Runs the built-in tests and publishes the status word if the interval has elapsed since the last publication.
+    /// Must be called periodically with the current time.
+    /// # Example
+    /// ```ignore
+    /// can_aerospace.poll_built_in_tests(now_ms())?;
+    /// ```
+    pub fn poll_built_in_tests(&mut self, now: Timestamp) -> Result<(), Error<D::Error>> {
+        let Some(message_type) = self.tests.status else {
+            return Ok(());
+        };
+        match self.tests.last {
+            _ if self.tests.interval == 0 => return Ok(()),
+            Some(last) if now.wrapping_sub(last) < self.tests.interval => return Ok(()),
+            _ => self.tests.last = Some(now),
+        }
+        let ids = self
+            .tests
+            .tests
+            .keys()
+            .copied()
+            .collect::<heapless::Vec<u8, TEST_TABLE_SIZE>>();
+        for id in ids {
+            self.tests.run(id);
+        }
+        self.send_message(CANAerospaceMessage {
+            message_type,
+            node_id: self.node_id,
+            service_code: ServiceCodeEnum::UNKNOWN,
+            message_code: 0,
+            data: DataType::BLONG(self.tests.status_word()),
+        })
+    }
+
+    /// Runs the requested test. Broadcasted requests are not answered and do not run any test.
+    pub(crate) fn handle_tcs(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        if request.node_id == 0 {
+            return None;
+        }
+        let response = match self.tests.run(request.message_code) {
+            Some(0) => ServiceResponse {
+                message_code: TCS_PASSED,
+                data: DataType::BLONG(0),
+            },
+            Some(code) => ServiceResponse {
+                message_code: TCS_FAILED,
+                data: DataType::BLONG(code),
+            },
+            None => ServiceResponse {
+                message_code: TCS_UNKNOWN_TEST,
+                data: DataType::NODATA,
+            },
+        };
+        Some(response)
+    }
+}
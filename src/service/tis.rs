@@ -0,0 +1,35 @@
+//! # CANAerospace - Transmission Interval Service
+//!
+//! [crate::types::ServiceCodeEnum::TIS] requests carry the identifier and the new transmission interval as
+//! [DataType::USHORT2]. An interval of 0 disables the transmission of the identifier.
+//! The response carries [TIS_OK] or [TIS_INVALID] in its `message_code` and no data.
+
+use crate::{
+    driver::CANAerospaceDriver, message::CANAerospaceMessage, service::ServiceResponse,
+    types::DataType, CANAerospaceLite,
+};
+
+/// `message_code` of the response to an accepted TIS request
+pub const TIS_OK: u8 = 0;
+/// `message_code` of the response to a TIS request for an identifier which is not scheduled or without [DataType::USHORT2]
+pub const TIS_INVALID: u8 = -1i8 as u8;
+
+impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+where
+    D: CANAerospaceDriver,
+{
+    /// Changes the interval of a scheduled identifier. Broadcasted requests are executed but not answered.
+    pub(crate) fn handle_tis(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+        let accepted = match request.data {
+            DataType::USHORT2(id, interval) => self.scheduler.set_interval(id, interval.into()),
+            _ => false,
+        };
+        if request.node_id == 0 {
+            return None;
+        }
+        Some(ServiceResponse {
+            message_code: if accepted { TIS_OK } else { TIS_INVALID },
+            data: DataType::NODATA,
+        })
+    }
+}
@@ -0,0 +1,174 @@
+//! # CANAerospace - Statistics
+//!
+//! Counters which describe the bus activity of [crate::CANAerospaceLite]
+
+/// Snapshot of the counters of a node, all counters wrap around on overflow.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NodeStatistics {
+    /// Frames returned by the driver
+    pub frames_received: u32,
+    /// Frames accepted by the driver for transmission
+    pub frames_sent: u32,
+    /// [crate::types::ServiceCodeEnum::IDS] requests which are answered
+    pub ids_requests_answered: u32,
+    /// Service requests which are passed to a registered [crate::service::ServiceHandler]
+    pub service_requests_handled: u32,
+    /// Service requests on channels the node does not listen on, which are passed to the receive queue
+    pub service_requests_queued: u32,
+    /// Received frames with an [crate::types::MessageType::INVALID] identifier
+    pub invalid_frames_discarded: u32,
+    /// Received frames which are dropped by the acceptance filters
+    pub frames_filtered: u32,
+    /// Received frames which did not fit into the receive queue
+    pub queue_overflows: u32,
+    /// Send or receive failures reported by the driver
+    pub driver_errors: u32,
+}
+
+/// Selects a single counter of [NodeStatistics]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatisticsCounter {
+    FramesReceived,
+    FramesSent,
+    IdsRequestsAnswered,
+    ServiceRequestsHandled,
+    ServiceRequestsQueued,
+    InvalidFramesDiscarded,
+    FramesFiltered,
+    QueueOverflows,
+    DriverErrors,
+}
+
+impl NodeStatistics {
+    /// Returns the value of the given counter
+    ///```
+    /// # use can_aerospace_lite::statistics::{NodeStatistics, StatisticsCounter};
+    /// let statistics = NodeStatistics { frames_sent: 5, ..NodeStatistics::default() };
+    /// assert_eq!(statistics.get(StatisticsCounter::FramesSent), 5);
+    /// assert_eq!(statistics.get(StatisticsCounter::DriverErrors), 0);
+    ///```
+    pub fn get(&self, counter: StatisticsCounter) -> u32 {
+        match counter {
+            StatisticsCounter::FramesReceived => self.frames_received,
+            StatisticsCounter::FramesSent => self.frames_sent,
+            StatisticsCounter::IdsRequestsAnswered => self.ids_requests_answered,
+            StatisticsCounter::ServiceRequestsHandled => self.service_requests_handled,
+            StatisticsCounter::ServiceRequestsQueued => self.service_requests_queued,
+            StatisticsCounter::InvalidFramesDiscarded => self.invalid_frames_discarded,
+            StatisticsCounter::FramesFiltered => self.frames_filtered,
+            StatisticsCounter::QueueOverflows => self.queue_overflows,
+            StatisticsCounter::DriverErrors => self.driver_errors,
+        }
+    }
+}
+
+/// Increments the counter by one, wrapping around on overflow
+pub(crate) fn count(counter: &mut u32) {
+    *counter = counter.wrapping_add(1);
+}
+
+#[cfg(feature = "statistics-publish")]
+pub(crate) use publish::StatisticsPublisher;
+#[cfg(feature = "statistics-publish")]
+pub use publish::STATISTICS_PUBLISH_SIZE;
+
+#[cfg(feature = "statistics-publish")]
+mod publish {
+    use heapless::Vec;
+
+    use super::StatisticsCounter;
+    use crate::{
+        driver::CANAerospaceDriver,
+        error::Error,
+        message::CANAerospaceMessage,
+        types::{DataType, MessageType, ServiceCodeEnum, Timestamp},
+        CANAerospaceLite,
+    };
+
+    /// Number of counters which can be published periodically
+    pub const STATISTICS_PUBLISH_SIZE: usize = 8;
+
+    /// Keeps which counters are published on which identifiers and when
+    #[derive(Debug, Default)]
+    pub(crate) struct StatisticsPublisher {
+        pub(crate) entries: Vec<(StatisticsCounter, MessageType), STATISTICS_PUBLISH_SIZE>,
+        pub(crate) interval: Timestamp,
+        pub(crate) last: Option<Timestamp>,
+    }
+
+    impl StatisticsPublisher {
+        /// Returns true and remembers `now` if counters must be published at `now`
+        pub(crate) fn is_due(&mut self, now: Timestamp) -> bool {
+            if self.interval == 0 || self.entries.is_empty() {
+                return false;
+            }
+            match self.last {
+                Some(last) if now.wrapping_sub(last) < self.interval => false,
+                _ => {
+                    self.last = Some(now);
+                    true
+                }
+            }
+        }
+    }
+
+    impl<'a, D, const N: usize> CANAerospaceLite<'a, D, N>
+    where
+        D: CANAerospaceDriver,
+    {
+        /// Adds `counter` to the counters which are published by [CANAerospaceLite::poll_statistics].
+        /// The counter is sent as [DataType::ULONG] on `message_type` which must be a
+        /// [MessageType::NOD] or [MessageType::UDL] identifier.
+        /// # Example
+        /// ```ignore
+        /// can_aerospace.publish_statistics_counter(StatisticsCounter::DriverErrors, MessageType::UDL(1850))?;
+        /// ```
+        pub fn publish_statistics_counter(
+            &mut self,
+            counter: StatisticsCounter,
+            message_type: MessageType,
+        ) -> Result<(), Error<D::Error>> {
+            if !matches!(message_type, MessageType::NOD(_) | MessageType::UDL(_)) {
+                return Err(Error::InvalidIdentifier);
+            }
+            self.statistics_publisher
+                .entries
+                .push((counter, message_type))
+                .map_err(|_| Error::TableFull)
+        }
+
+        /// Sets the interval of publishing counters in the unit of the timestamps given to
+        /// [CANAerospaceLite::poll_statistics]. Publishing is disabled when the interval is 0, which is the default.
+        /// # Example
+        /// ```ignore
+        /// can_aerospace.set_statistics_interval(1000);
+        /// ```
+        pub fn set_statistics_interval(&mut self, interval: Timestamp) {
+            self.statistics_publisher.interval = interval;
+        }
+
+        /// Sends all registered counters if the statistics interval has elapsed since the last publication.
+        /// Must be called periodically with the current time.
+        /// # Example
+        /// ```ignore
+        /// can_aerospace.poll_statistics(now_ms())?;
+        /// ```
+        pub fn poll_statistics(&mut self, now: Timestamp) -> Result<(), Error<D::Error>> {
+            if !self.statistics_publisher.is_due(now) {
+                return Ok(());
+            }
+            for i in 0..self.statistics_publisher.entries.len() {
+                let (counter, message_type) = self.statistics_publisher.entries[i];
+                let message = CANAerospaceMessage {
+                    message_type,
+                    node_id: self.node_id,
+                    service_code: ServiceCodeEnum::UNKNOWN,
+                    message_code: 0,
+                    data: DataType::ULONG(self.statistics.get(counter)),
+                };
+                self.send_message(message)?;
+            }
+            Ok(())
+        }
+    }
+}
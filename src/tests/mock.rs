@@ -0,0 +1,147 @@
+//! # CANAerospace{Lite} - mock
+//!
+//! Driver mock which keeps its own state, so tests using it can run in parallel
+
+#![cfg(test)]
+
+use heapless::{Deque, Vec};
+
+use crate::{
+    config::ConfigStore,
+    driver::CANAerospaceDriver,
+    message::{CANAerospaceFrame, Payload, RawMessage},
+    types::{Bitrate, DataType, IDSConfiguration, MessageType, ServiceCodeEnum},
+    CANAerospaceLite,
+};
+
+/// Records every sent frame and returns queued frames on receive
+#[derive(Debug, Default)]
+pub struct MockDriver {
+    pub sent: Vec<CANAerospaceFrame, 64>,
+    pub incoming: Deque<CANAerospaceFrame, 64>,
+    pub recv_calls: usize,
+    pub fail_send: bool,
+    pub fail_recv: bool,
+    /// Bit rates accepted by `set_bitrate`
+    pub bitrates: Vec<Bitrate, 4>,
+    /// Bit rate changes with the number of frames which were sent before
+    pub bitrate_changes: Vec<(Bitrate, usize), 8>,
+}
+
+impl MockDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a frame which will be returned by the next `recv_frame` call
+    pub fn queue(&mut self, frame: CANAerospaceFrame) {
+        self.incoming.push_back(frame).unwrap();
+    }
+}
+
+/// Error reported by [MockDriver] when a failure is injected
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MockError;
+
+impl CANAerospaceDriver for MockDriver {
+    type Error = MockError;
+
+    fn send_frame(&mut self, frame: CANAerospaceFrame) -> Result<(), MockError> {
+        if self.fail_send {
+            return Err(MockError);
+        }
+        self.sent.push(frame).unwrap();
+        Ok(())
+    }
+
+    fn recv_frame(&mut self) -> Result<Option<CANAerospaceFrame>, MockError> {
+        self.recv_calls += 1;
+        if self.fail_recv {
+            return Err(MockError);
+        }
+        Ok(self.incoming.pop_front())
+    }
+
+    fn supports_bitrate(&self, bitrate: Bitrate) -> bool {
+        self.bitrates.contains(&bitrate)
+    }
+
+    fn set_bitrate(&mut self, bitrate: Bitrate) -> Result<(), MockError> {
+        self.bitrate_changes
+            .push((bitrate, self.sent.len()))
+            .unwrap();
+        Ok(())
+    }
+}
+
+/// Config store which records the settings stored by the services
+#[derive(Debug, Default)]
+pub struct Eeprom {
+    pub bitrate: Option<Bitrate>,
+    pub node_id: Option<u8>,
+    pub parameters: Vec<(u8, DataType), 4>,
+    pub remaps: Vec<(u16, u16), 4>,
+    pub distribution: Option<IDSConfiguration>,
+}
+
+impl ConfigStore for Eeprom {
+    fn store_bitrate(&mut self, bitrate: Bitrate) {
+        self.bitrate = Some(bitrate);
+    }
+
+    fn store_node_id(&mut self, node_id: u8) {
+        self.node_id = Some(node_id);
+    }
+
+    fn store_parameter(&mut self, index: u8, value: DataType) {
+        self.parameters.push((index, value)).unwrap();
+    }
+
+    fn store_remap(&mut self, logical: u16, actual: u16) {
+        self.remaps.push((logical, actual)).unwrap();
+    }
+
+    fn store_id_distribution(&mut self, configuration: IDSConfiguration) {
+        self.distribution = Some(configuration);
+    }
+}
+
+/// Returns a request of `service` to `node_id` on the first high priority channel
+pub fn request(
+    node_id: u8,
+    service: ServiceCodeEnum,
+    message_code: u8,
+    data: DataType,
+) -> CANAerospaceFrame {
+    CANAerospaceFrame {
+        message_type: MessageType::NSH(128),
+        message: RawMessage {
+            node_id,
+            data_type: data.type_id(),
+            service_code: service.as_u8(),
+            message_code,
+            payload: Payload::from(&data),
+        },
+    }
+}
+
+/// Passes `frame` to the node and returns the last frame which the node sent while handling it
+pub fn receive<const N: usize>(
+    canas: &mut CANAerospaceLite<'_, MockDriver, N>,
+    frame: CANAerospaceFrame,
+) -> Option<RawMessage> {
+    let sent = canas.driver.sent.len();
+    canas.driver.queue(frame);
+    canas.notify_receive_event().unwrap();
+    canas.driver.sent[sent..]
+        .last()
+        .map(|frame| frame.message.clone())
+}
+
+/// Passes `frame` to the node and returns the `message_code` of its response
+pub fn response_code<const N: usize>(
+    canas: &mut CANAerospaceLite<'_, MockDriver, N>,
+    frame: CANAerospaceFrame,
+) -> u8 {
+    receive(canas, frame).unwrap().message_code
+}
@@ -2,8 +2,30 @@
 //!
 //! Unit tests of whole library
 
+mod mock;
+mod test_bss;
 #[cfg(feature = "bxcan-support")]
 mod test_bxcan;
+mod test_client;
+mod test_css;
+mod test_dds;
+mod test_dss;
+mod test_dus;
+mod test_filter;
+mod test_fps;
+mod test_fss;
 mod test_lib;
+mod test_mcs;
 mod test_message;
+mod test_mis;
+mod test_nis;
+mod test_nss;
+mod test_queue;
+mod test_scheduler;
+mod test_scs;
+mod test_service;
+mod test_statistics;
+mod test_sts;
+mod test_tcs;
+mod test_tis;
 mod test_types;
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod baudratesetting {
+    use crate::{
+        service::bss::{BSS_INVALID, BSS_OK},
+        tests::mock::{request, Eeprom, MockDriver},
+        types::{Bitrate, DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_bitrate_codes() {
+        for code in 0..4 {
+            let bitrate = Bitrate::from_code(code).unwrap();
+            assert_eq!(bitrate.code(), code);
+        }
+        assert_eq!(Bitrate::from_code(0xFF), None);
+        assert_eq!(Bitrate::Kbps1000.bits_per_second(), 1_000_000);
+        assert_eq!(Bitrate::Kbps125.bits_per_second(), 125_000);
+    }
+
+    #[test]
+    fn test_set_bitrate_after_response() {
+        let mut eeprom = Eeprom::default();
+        let mut driver = MockDriver::new();
+        driver.bitrates.push(Bitrate::Kbps500).unwrap();
+        let mut canas = CANAerospaceLite::new(10, driver);
+        canas.set_config_store(&mut eeprom);
+
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::BSS,
+            Bitrate::Kbps500.code(),
+            DataType::NODATA,
+        ));
+        canas.notify_receive_event().unwrap();
+        let response = &canas.driver.sent[0];
+        assert_eq!(response.message_type, MessageType::NSH(129));
+        assert_eq!(response.message.service_code, ServiceCodeEnum::BSS.as_u8());
+        assert_eq!(response.message.message_code, BSS_OK);
+        assert_eq!(
+            canas.driver.bitrate_changes.as_slice(),
+            &[(Bitrate::Kbps500, 1)]
+        );
+        drop(canas);
+        assert_eq!(eeprom.bitrate, Some(Bitrate::Kbps500));
+    }
+
+    #[test]
+    fn test_invalid_bitrate() {
+        let mut driver = MockDriver::new();
+        driver.bitrates.push(Bitrate::Kbps500).unwrap();
+        let mut canas = CANAerospaceLite::new(10, driver);
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::BSS, 4, DataType::NODATA));
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::BSS,
+            Bitrate::Kbps1000.code(),
+            DataType::NODATA,
+        ));
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert!(canas
+            .driver
+            .sent
+            .iter()
+            .all(|response| response.message.message_code == BSS_INVALID));
+        assert!(canas.driver.bitrate_changes.is_empty());
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let mut driver = MockDriver::new();
+        driver.bitrates.push(Bitrate::Kbps250).unwrap();
+        let mut canas = CANAerospaceLite::new(10, driver);
+
+        canas.driver.queue(request(
+            0,
+            ServiceCodeEnum::BSS,
+            Bitrate::Kbps250.code(),
+            DataType::NODATA,
+        ));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.driver.sent.is_empty());
+        assert_eq!(
+            canas.driver.bitrate_changes.as_slice(),
+            &[(Bitrate::Kbps250, 0)]
+        );
+    }
+
+    #[test]
+    fn test_not_switched_if_response_fails() {
+        let mut driver = MockDriver::new();
+        driver.bitrates.push(Bitrate::Kbps250).unwrap();
+        driver.fail_send = true;
+        let mut canas = CANAerospaceLite::new(10, driver);
+
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::BSS,
+            Bitrate::Kbps250.code(),
+            DataType::NODATA,
+        ));
+        assert!(canas.notify_receive_event().is_err());
+        canas.driver.fail_send = false;
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::BSS, 0xFF, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.driver.bitrate_changes.is_empty());
+    }
+}
@@ -54,6 +54,51 @@ mod frame {
     }
 }
 
+#[cfg(test)]
+mod transmit {
+    use bxcan::{Frame, StandardId};
+    use heapless::Vec;
+
+    use crate::bxcan::{transmit_displacing, BxcanError};
+
+    fn frame(id: u16) -> Frame {
+        Frame::new_data(StandardId::new(id).unwrap(), [0; 8])
+    }
+
+    fn transmit(
+        results: [nb::Result<Option<Frame>, core::convert::Infallible>; 2],
+    ) -> (Result<(), BxcanError>, Vec<Frame, 2>) {
+        let mut results = IntoIterator::into_iter(results);
+        let mut queued = Vec::new();
+        let result = transmit_displacing(&frame(200), |frame| {
+            queued.push(frame.clone()).unwrap();
+            results.next().unwrap()
+        });
+        (result, queued)
+    }
+
+    #[test]
+    fn test_displaced_frame_is_queued_again() {
+        let (result, queued) = transmit([Ok(Some(frame(300))), Ok(None)]);
+        assert_eq!(result, Ok(()));
+        assert_eq!(queued, [frame(200), frame(300)]);
+    }
+
+    #[test]
+    fn test_displaced_frame_lost() {
+        let (result, queued) = transmit([Ok(Some(frame(300))), Err(nb::Error::WouldBlock)]);
+        assert_eq!(result, Err(BxcanError::Displaced));
+        assert_eq!(queued, [frame(200), frame(300)]);
+    }
+
+    #[test]
+    fn test_mailboxes_full() {
+        let (result, queued) = transmit([Err(nb::Error::WouldBlock), Ok(None)]);
+        assert_eq!(result, Err(BxcanError::WouldBlock));
+        assert_eq!(queued, [frame(200)]);
+    }
+}
+
 #[cfg(test)]
 mod data {
     use bxcan::Data;
@@ -83,3 +128,64 @@ mod data {
         assert_eq!(data[7], p[3]);
     }
 }
+
+#[cfg(test)]
+mod filterbanks {
+    use crate::filter::{AcceptanceFilter, FilterTable, MessageClass};
+
+    fn accepted(masks: &[(u16, u16)], id: u16) -> bool {
+        masks
+            .iter()
+            .any(|(filter, mask)| id & mask == filter & mask)
+    }
+
+    #[test]
+    fn test_accept_all_without_filters() {
+        let table = FilterTable::default();
+        let masks = table.hardware_masks::<4>().unwrap();
+        assert_eq!(masks, [(0, 0)]);
+    }
+
+    #[test]
+    fn test_masks_match_filters() {
+        let mut table = FilterTable::default();
+        table
+            .add(AcceptanceFilter::Range {
+                first: 301,
+                last: 517,
+            })
+            .ok()
+            .unwrap();
+        table
+            .add(AcceptanceFilter::Mask {
+                id: 0x7A3,
+                mask: 0x7F0,
+            })
+            .ok()
+            .unwrap();
+        table
+            .add(AcceptanceFilter::Class(MessageClass::EED))
+            .ok()
+            .unwrap();
+        let masks = table.hardware_masks::<56>().unwrap();
+        for id in 0..=0x7FF {
+            let expected = table.filters.iter().any(|filter| filter.matches(id))
+                || MessageClass::NSH.range().0 <= id && id <= MessageClass::NSH.range().1
+                || MessageClass::NSL.range().0 <= id && id <= MessageClass::NSL.range().1;
+            assert_eq!(accepted(&masks, id), expected, "id {}", id);
+        }
+    }
+
+    #[test]
+    fn test_too_many_masks() {
+        let mut table = FilterTable::default();
+        table
+            .add(AcceptanceFilter::Range {
+                first: 1,
+                last: 2046,
+            })
+            .ok()
+            .unwrap();
+        assert!(table.hardware_masks::<8>().is_none());
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod serviceclient {
+    use crate::{
+        client::{RequestStatus, ScannedNode, PENDING_REQUEST_TABLE_SIZE},
+        error::Error,
+        message::{CANAerospaceFrame, Payload, RawMessage},
+        service::ServiceResponse,
+        tests::mock::MockDriver,
+        types::{
+            ChannelPriority, DataType, IDSResponse, MessageType, ServiceChannel, ServiceCodeEnum,
+        },
+        CANAerospaceLite,
+    };
+
+    fn high(number: u8) -> ServiceChannel {
+        ServiceChannel::new(ChannelPriority::High, number).unwrap()
+    }
+
+    fn response(
+        channel: MessageType,
+        node_id: u8,
+        service: ServiceCodeEnum,
+        code: u8,
+        data: DataType,
+    ) -> CANAerospaceFrame {
+        CANAerospaceFrame {
+            message_type: channel,
+            message: RawMessage {
+                node_id,
+                data_type: data.type_id(),
+                service_code: service.as_u8(),
+                message_code: code,
+                payload: Payload::from(&data),
+            },
+        }
+    }
+
+    #[test]
+    fn test_send_request() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let id = canas
+            .send_request(
+                0x20,
+                ServiceChannel::new(ChannelPriority::Low, 1).unwrap(),
+                ServiceCodeEnum::TIS,
+                0,
+                DataType::USHORT2(315, 100),
+                0,
+                0,
+            )
+            .unwrap();
+        let request = &canas.driver.sent[0];
+        assert_eq!(request.message_type, MessageType::NSL(2002));
+        assert_eq!(request.message.node_id, 0x20);
+        assert_eq!(request.message.service_code, ServiceCodeEnum::TIS.as_u8());
+        assert_eq!(canas.request_status(id), Some(RequestStatus::Pending));
+        assert_eq!(canas.take_response(id), Some(RequestStatus::Pending));
+
+        canas.driver.queue(response(
+            MessageType::NSL(2003),
+            0x20,
+            ServiceCodeEnum::TIS,
+            0,
+            DataType::NODATA,
+        ));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.read_message().is_none());
+        let answer = RequestStatus::Answered(ServiceResponse {
+            message_code: 0,
+            data: DataType::NODATA,
+        });
+        assert_eq!(canas.take_response(id), Some(answer));
+        assert_eq!(canas.take_response(id), None);
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let request = |canas: &mut CANAerospaceLite<MockDriver>, node, channel, service| {
+            canas.send_request(node, channel, service, 0, DataType::NODATA, 0, 0)
+        };
+        assert_eq!(
+            request(&mut canas, 0, high(0), ServiceCodeEnum::IDS),
+            Err(Error::InvalidNodeId)
+        );
+        assert_eq!(
+            request(&mut canas, 0x20, high(0), ServiceCodeEnum::UNKNOWN),
+            Err(Error::InvalidServiceCode)
+        );
+        for _ in 0..PENDING_REQUEST_TABLE_SIZE {
+            request(&mut canas, 0x20, high(0), ServiceCodeEnum::IDS).unwrap();
+        }
+        assert_eq!(
+            request(&mut canas, 0x20, high(0), ServiceCodeEnum::IDS),
+            Err(Error::TableFull)
+        );
+        assert_eq!(canas.driver.sent.len(), PENDING_REQUEST_TABLE_SIZE);
+
+        canas.driver.fail_send = true;
+        let id = request(&mut canas, 0x20, high(0), ServiceCodeEnum::IDS);
+        assert!(id.is_err());
+    }
+
+    #[test]
+    fn test_matching() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let request = |canas: &mut CANAerospaceLite<MockDriver>, node, code| {
+            canas
+                .send_request(
+                    node,
+                    high(0),
+                    ServiceCodeEnum::MCS,
+                    code,
+                    DataType::NODATA,
+                    0,
+                    0,
+                )
+                .unwrap()
+        };
+        let first = request(&mut canas, 0x20, 1);
+        let second = request(&mut canas, 0x20, 2);
+        let other_node = request(&mut canas, 0x21, 2);
+
+        // responses of other nodes, services or channels are not matched
+        canas.driver.queue(response(
+            MessageType::NSH(129),
+            0x22,
+            ServiceCodeEnum::MCS,
+            2,
+            DataType::UCHAR(1),
+        ));
+        canas.driver.queue(response(
+            MessageType::NSH(129),
+            0x20,
+            ServiceCodeEnum::IDS,
+            2,
+            DataType::UCHAR(1),
+        ));
+        canas.driver.queue(response(
+            MessageType::NSH(131),
+            0x20,
+            ServiceCodeEnum::MCS,
+            2,
+            DataType::UCHAR(1),
+        ));
+        // message code selects the request, otherwise the oldest one is answered
+        canas.driver.queue(response(
+            MessageType::NSH(129),
+            0x20,
+            ServiceCodeEnum::MCS,
+            2,
+            DataType::UCHAR(2),
+        ));
+        canas.driver.queue(response(
+            MessageType::NSH(129),
+            0x20,
+            ServiceCodeEnum::MCS,
+            0xFF,
+            DataType::NODATA,
+        ));
+        for _ in 0..5 {
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.rx_queue.len(), 3);
+        assert_eq!(
+            canas.request_status(second),
+            Some(RequestStatus::Answered(ServiceResponse {
+                message_code: 2,
+                data: DataType::UCHAR(2),
+            }))
+        );
+        assert_eq!(
+            canas.request_status(first),
+            Some(RequestStatus::Answered(ServiceResponse {
+                message_code: 0xFF,
+                data: DataType::NODATA,
+            }))
+        );
+        assert_eq!(
+            canas.request_status(other_node),
+            Some(RequestStatus::Pending)
+        );
+
+        canas.cancel_request(other_node);
+        assert_eq!(canas.request_status(other_node), None);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let request = |canas: &mut CANAerospaceLite<MockDriver>, timeout| {
+            canas
+                .send_request(
+                    0x20,
+                    high(0),
+                    ServiceCodeEnum::IDS,
+                    0,
+                    DataType::NODATA,
+                    timeout,
+                    u32::MAX - 10,
+                )
+                .unwrap()
+        };
+        let short = request(&mut canas, 100);
+        let endless = request(&mut canas, 0);
+        // the timeout starts when the request is sent, not at the first poll
+        canas.poll_requests(88);
+        assert_eq!(canas.request_status(short), Some(RequestStatus::Pending));
+        canas.poll_requests(89);
+        assert_eq!(canas.take_response(short), Some(RequestStatus::TimedOut));
+        canas.poll_requests(10_000);
+        assert_eq!(canas.request_status(endless), Some(RequestStatus::Pending));
+
+        // late responses are queued
+        canas.cancel_request(endless);
+        canas.driver.queue(response(
+            MessageType::NSH(129),
+            0x20,
+            ServiceCodeEnum::IDS,
+            0,
+            DataType::UCHAR4(1, 2, 0, 0),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.read_message().is_some());
+    }
+
+    #[test]
+    fn test_ids_scan() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.ids_scan(high(0), 100, 1000).unwrap();
+        let request = &canas.driver.sent[0];
+        assert_eq!(request.message.node_id, 0);
+        assert_eq!(request.message.service_code, ServiceCodeEnum::IDS.as_u8());
+        assert_eq!(canas.ids_scan_result(), None);
+
+        let ids = |node_id, hw_rev| {
+            response(
+                MessageType::NSH(129),
+                node_id,
+                ServiceCodeEnum::IDS,
+                0,
+                DataType::UCHAR4(hw_rev, 1, 0, 0),
+            )
+        };
+        canas.driver.queue(ids(0x20, 2));
+        canas.driver.queue(ids(0x21, 3));
+        canas.driver.queue(ids(0x20, 4));
+        canas.poll_requests(1050);
+        for _ in 0..3 {
+            canas.notify_receive_event().unwrap();
+        }
+        canas.poll_requests(1099);
+        assert_eq!(canas.ids_scan_result(), None);
+        canas.poll_requests(1100);
+
+        // responses after the window are queued
+        canas.driver.queue(ids(0x22, 5));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.read_message().is_some());
+
+        let nodes = canas.ids_scan_result().unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(
+            nodes[0],
+            ScannedNode {
+                node_id: 0x20,
+                identification: IDSResponse::from_data(DataType::UCHAR4(2, 1, 0, 0)).unwrap(),
+                duplicate: true,
+            }
+        );
+        assert_eq!(nodes[1].node_id, 0x21);
+        assert_eq!(nodes[1].identification.hw_rev.0, 3);
+        assert!(!nodes[1].duplicate);
+    }
+
+    #[test]
+    fn test_request_during_ids_scan() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.ids_scan(high(0), 100, 0).unwrap();
+        let id = canas
+            .send_request(
+                0x20,
+                high(0),
+                ServiceCodeEnum::IDS,
+                0,
+                DataType::NODATA,
+                100,
+                0,
+            )
+            .unwrap();
+
+        // the node answers the scan and the request
+        for hw_rev in [2, 3] {
+            canas.driver.queue(response(
+                MessageType::NSH(129),
+                0x20,
+                ServiceCodeEnum::IDS,
+                0,
+                DataType::UCHAR4(hw_rev, 1, 0, 0),
+            ));
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(
+            canas.take_response(id),
+            Some(RequestStatus::Answered(ServiceResponse {
+                message_code: 0,
+                data: DataType::UCHAR4(2, 1, 0, 0),
+            }))
+        );
+        canas.poll_requests(100);
+        let nodes = canas.ids_scan_result().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].node_id, 0x20);
+        assert!(!nodes[0].duplicate);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod canidsetting {
+    use crate::{
+        error::Error,
+        message::{CANAerospaceFrame, CANAerospaceMessage, Payload, RawMessage},
+        service::css::{CSS_INVALID, CSS_OK, CSS_TABLE_FULL, REMAP_TABLE_SIZE},
+        tests::mock::{request, response_code, Eeprom, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    fn nod(id: u16) -> CANAerospaceFrame {
+        let data = DataType::ULONG(id.into());
+        CANAerospaceFrame {
+            message_type: MessageType::NOD(id),
+            message: RawMessage {
+                node_id: 20,
+                data_type: data.type_id(),
+                service_code: 0,
+                message_code: 0,
+                payload: Payload::from(&data),
+            },
+        }
+    }
+
+    #[test]
+    fn test_remap() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .remap_identifier(MessageType::NOD(315), MessageType::NOD(316))
+            .unwrap();
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(315)),
+            MessageType::NOD(316)
+        );
+        assert_eq!(
+            canas.remap_identifier(MessageType::NOD(400), MessageType::NOD(316)),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            canas.remap_identifier(MessageType::NOD(400), MessageType::NSH(128)),
+            Err(Error::InvalidIdentifier)
+        );
+
+        canas
+            .send_message(CANAerospaceMessage::from(nod(315)))
+            .unwrap();
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NOD(316));
+
+        canas.driver.queue(nod(316));
+        canas.driver.queue(nod(317));
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        let message = canas.read_message().unwrap();
+        assert_eq!(message.message_type, MessageType::NOD(315));
+        assert_eq!(message.data, DataType::ULONG(316));
+        let message = canas.read_message().unwrap();
+        assert_eq!(message.message_type, MessageType::NOD(317));
+
+        canas
+            .remap_identifier(MessageType::NOD(315), MessageType::NOD(315))
+            .unwrap();
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(315)),
+            MessageType::NOD(315)
+        );
+    }
+
+    #[test]
+    fn test_table_full() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for id in 0..REMAP_TABLE_SIZE as u16 {
+            canas
+                .remap_identifier(MessageType::NOD(300 + id), MessageType::NOD(1000 + id))
+                .unwrap();
+        }
+        assert_eq!(
+            canas.remap_identifier(MessageType::NOD(400), MessageType::NOD(1100)),
+            Err(Error::TableFull)
+        );
+        // existing remaps can still be changed
+        canas
+            .remap_identifier(MessageType::NOD(300), MessageType::NOD(1100))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_css_request() {
+        let mut eeprom = Eeprom::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_config_store(&mut eeprom);
+
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::CSS, 0, DataType::USHORT2(315, 316)),
+        );
+        assert_eq!(code, CSS_OK);
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NSH(129));
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(315)),
+            MessageType::NOD(316)
+        );
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::CSS, 0, DataType::USHORT2(315, 2000)),
+        );
+        assert_eq!(code, CSS_INVALID);
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::CSS, 0, DataType::ULONG(315)),
+        );
+        assert_eq!(code, CSS_INVALID);
+        for id in 1..REMAP_TABLE_SIZE as u16 {
+            canas
+                .remap_identifier(MessageType::NOD(400 + id), MessageType::NOD(1000 + id))
+                .unwrap();
+        }
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::CSS, 0, DataType::USHORT2(500, 501)),
+        );
+        assert_eq!(code, CSS_TABLE_FULL);
+
+        canas.driver.queue(request(
+            0,
+            ServiceCodeEnum::CSS,
+            0,
+            DataType::USHORT2(315, 315),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 4);
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(315)),
+            MessageType::NOD(316)
+        );
+        drop(canas);
+        assert_eq!(eeprom.remaps.as_slice(), &[(315, 316)]);
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod datadownload {
+    use crate::{
+        service::{
+            dds::{MemoryTarget, DDS_ABORT, DDS_XOFF, DDS_XON},
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{receive, request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[derive(Default)]
+    struct RamTarget {
+        memid: Option<u32>,
+        data: heapless::Vec<u8, 32>,
+        committed: bool,
+        aborted: bool,
+        busy: bool,
+    }
+
+    impl MemoryTarget for RamTarget {
+        fn begin(&mut self, memid: u32, _blocks: u8) -> bool {
+            if memid != 0x10 {
+                return false;
+            }
+            self.memid = Some(memid);
+            self.data.clear();
+            true
+        }
+
+        fn write(&mut self, _memid: u32, _block: u8, data: &[u8]) -> bool {
+            self.data.extend_from_slice(data).is_ok()
+        }
+
+        fn ready(&self) -> bool {
+            !self.busy
+        }
+
+        fn commit(&mut self, _memid: u32) -> bool {
+            self.committed = true;
+            true
+        }
+
+        fn abort(&mut self, _memid: u32) {
+            self.aborted = true;
+        }
+    }
+
+    fn last_code(canas: &CANAerospaceLite<MockDriver>) -> u8 {
+        canas.driver.sent.last().unwrap().message.message_code
+    }
+
+    #[test]
+    fn test_without_target() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x10)),
+        );
+        assert_eq!(last_code(&canas), SERVICE_NOT_SUPPORTED);
+    }
+
+    #[test]
+    fn test_download() {
+        let mut target = RamTarget::default();
+        {
+            let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+            canas.set_memory_target(&mut target);
+
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x10)),
+            );
+            assert_eq!(canas.driver.sent.len(), 1);
+            assert_eq!(canas.driver.sent[0].message_type, MessageType::NSH(129));
+            assert_eq!(last_code(&canas), DDS_XON);
+            assert!(canas.dds_in_progress());
+
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 0, DataType::ULONG(0x01020304)),
+            );
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 1, DataType::UCHAR2(5, 6)),
+            );
+            assert_eq!(canas.driver.sent.len(), 1);
+
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::CHKSUM(21)),
+            );
+            assert_eq!(canas.driver.sent.len(), 2);
+            assert_eq!(last_code(&canas), DDS_XOFF);
+            assert!(!canas.dds_in_progress());
+        }
+        assert_eq!(target.data, [1, 2, 3, 4, 5, 6]);
+        assert!(target.committed);
+        assert!(!target.aborted);
+    }
+
+    #[test]
+    fn test_unknown_memory() {
+        let mut target = RamTarget::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_memory_target(&mut target);
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x20)),
+        );
+        assert_eq!(last_code(&canas), DDS_ABORT);
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 0, DataType::MEMID(0x10)),
+        );
+        assert_eq!(last_code(&canas), DDS_ABORT);
+        assert!(!canas.dds_in_progress());
+    }
+
+    #[test]
+    fn test_sequence_error() {
+        let mut target = RamTarget::default();
+        {
+            let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+            canas.set_memory_target(&mut target);
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x10)),
+            );
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 1, DataType::ULONG(1)),
+            );
+            assert_eq!(last_code(&canas), DDS_ABORT);
+            assert!(!canas.dds_in_progress());
+
+            // data without a transfer in progress
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 0, DataType::ULONG(1)),
+            );
+            assert_eq!(canas.driver.sent.len(), 3);
+            assert_eq!(last_code(&canas), DDS_ABORT);
+        }
+        assert!(target.aborted);
+        assert!(!target.committed);
+    }
+
+    #[test]
+    fn test_checksum_error() {
+        let mut target = RamTarget::default();
+        {
+            let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+            canas.set_memory_target(&mut target);
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 1, DataType::MEMID(0x10)),
+            );
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 0, DataType::UCHAR(7)),
+            );
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 1, DataType::CHKSUM(8)),
+            );
+            assert_eq!(last_code(&canas), DDS_ABORT);
+        }
+        assert!(target.aborted);
+        assert!(!target.committed);
+    }
+
+    #[test]
+    fn test_flow_control() {
+        struct SlowTarget<'b>(&'b core::cell::Cell<bool>);
+        impl MemoryTarget for SlowTarget<'_> {
+            fn begin(&mut self, _: u32, _: u8) -> bool {
+                true
+            }
+            fn write(&mut self, _: u32, _: u8, _: &[u8]) -> bool {
+                self.0.set(true);
+                true
+            }
+            fn ready(&self) -> bool {
+                !self.0.get()
+            }
+            fn commit(&mut self, _: u32) -> bool {
+                true
+            }
+            fn abort(&mut self, _: u32) {}
+        }
+
+        let busy = core::cell::Cell::new(false);
+        let mut target = SlowTarget(&busy);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_memory_target(&mut target);
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x10)),
+        );
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 0, DataType::ULONG(1)),
+        );
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(last_code(&canas), DDS_XOFF);
+
+        canas.poll_dds(0).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+
+        busy.set(false);
+        canas.poll_dds(1).unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert_eq!(canas.driver.sent[2].message_type, MessageType::NSH(129));
+        assert_eq!(last_code(&canas), DDS_XON);
+        assert!(canas.dds_in_progress());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut target = RamTarget::default();
+        {
+            let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+            canas.set_memory_target(&mut target);
+            canas.set_dds_timeout(100);
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x10)),
+            );
+
+            canas.poll_dds(u32::MAX - 49).unwrap();
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 0, DataType::ULONG(1)),
+            );
+            // activity restarts the timeout
+            canas.poll_dds(40).unwrap();
+            canas.poll_dds(139).unwrap();
+            assert!(canas.dds_in_progress());
+            assert_eq!(canas.driver.sent.len(), 1);
+
+            canas.poll_dds(140).unwrap();
+            assert!(!canas.dds_in_progress());
+            assert_eq!(canas.driver.sent.len(), 2);
+            assert_eq!(last_code(&canas), DDS_ABORT);
+        }
+        assert!(target.aborted);
+    }
+
+    #[test]
+    fn test_broadcast_ignored() {
+        let mut target = RamTarget::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_memory_target(&mut target);
+        let mut frame = request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(0x10));
+        frame.message.node_id = 0;
+        receive(&mut canas, frame);
+        assert_eq!(canas.driver.sent.len(), 0);
+        assert!(!canas.dds_in_progress());
+    }
+}
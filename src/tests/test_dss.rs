@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod distributionsetting {
+    use crate::{
+        error::Error,
+        message::{CANAerospaceFrame, CANAerospaceMessage, Payload, RawMessage},
+        service::dss::{DISTRIBUTION_TABLE_SIZE, DSS_INVALID, DSS_OK},
+        tests::mock::{request, Eeprom, MockDriver},
+        types::{DataType, IDSConfiguration, MessageType, ServiceCodeEnum},
+        CANAerospaceLite, IDS_CONF_STANDARD,
+    };
+
+    static TRAINER: [(u16, u16); 2] = [(300, 1200), (301, 1201)];
+
+    fn nod(id: u16) -> CANAerospaceFrame {
+        let data = DataType::ULONG(id.into());
+        CANAerospaceFrame {
+            message_type: MessageType::from(id),
+            message: RawMessage {
+                node_id: 20,
+                data_type: data.type_id(),
+                service_code: 0,
+                message_code: 0,
+                payload: Payload::from(&data),
+            },
+        }
+    }
+
+    #[test]
+    fn test_register() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.register_id_distribution(IDS_CONF_STANDARD, &TRAINER),
+            Err(Error::InvalidDistribution)
+        );
+        assert_eq!(
+            canas.register_id_distribution(IDSConfiguration(1), &[(300, 128)]),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            canas.register_id_distribution(IDSConfiguration(1), &[(300, 1200), (301, 1200)]),
+            Err(Error::InvalidIdentifier)
+        );
+        for number in 1..=DISTRIBUTION_TABLE_SIZE as u8 {
+            canas
+                .register_id_distribution(IDSConfiguration(number), &TRAINER)
+                .unwrap();
+        }
+        assert_eq!(
+            canas.register_id_distribution(IDSConfiguration(0xFF), &TRAINER),
+            Err(Error::TableFull)
+        );
+    }
+
+    #[test]
+    fn test_routing() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .register_id_distribution(IDSConfiguration(1), &TRAINER)
+            .unwrap();
+        canas.set_ids_configuration(IDSConfiguration(1)).unwrap();
+        assert_eq!(canas.ids_configuration(), IDSConfiguration(1));
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(300)),
+            MessageType::NOD(1200)
+        );
+
+        canas
+            .send_message(CANAerospaceMessage::from(nod(301)))
+            .unwrap();
+        canas
+            .send_message(CANAerospaceMessage::from(nod(302)))
+            .unwrap();
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NOD(1201));
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NOD(302));
+
+        canas.driver.queue(nod(1200));
+        canas.notify_receive_event().unwrap();
+        let message = canas.read_message().unwrap();
+        assert_eq!(message.message_type, MessageType::NOD(300));
+
+        // remaps take precedence over the distribution
+        canas
+            .remap_identifier(MessageType::NOD(300), MessageType::NOD(1300))
+            .unwrap();
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(300)),
+            MessageType::NOD(1300)
+        );
+
+        canas.set_ids_configuration(IDS_CONF_STANDARD).unwrap();
+        assert_eq!(
+            canas.actual_identifier(MessageType::NOD(301)),
+            MessageType::NOD(301)
+        );
+    }
+
+    #[test]
+    fn test_dss_request() {
+        let mut eeprom = Eeprom::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_config_store(&mut eeprom);
+        canas
+            .register_id_distribution(IDSConfiguration(1), &TRAINER)
+            .unwrap();
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DSS, 2, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DSS, 1, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::IDS, 0, DataType::NODATA));
+        for _ in 0..3 {
+            canas.notify_receive_event().unwrap();
+        }
+        let sent = &canas.driver.sent;
+        assert_eq!(sent[0].message_type, MessageType::NSH(129));
+        assert_eq!(sent[0].message.message_code, DSS_INVALID);
+        assert_eq!(sent[1].message.message_code, DSS_OK);
+        assert_eq!(sent[2].message.payload.data[2], 1);
+
+        // broadcasts switch all nodes without response
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::DSS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert_eq!(canas.ids_configuration(), IDS_CONF_STANDARD);
+        drop(canas);
+        assert_eq!(eeprom.distribution, Some(IDS_CONF_STANDARD));
+    }
+}
@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod dataupload {
+    use crate::{
+        message::{CANAerospaceFrame, Payload, RawMessage},
+        service::{
+            dus::{MemorySource, UploadError, UploadStatus, DUS_ABORT},
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{request, MockDriver},
+        types::{ChannelPriority, DataType, MessageType, ServiceChannel, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    fn channel() -> ServiceChannel {
+        ServiceChannel::new(ChannelPriority::High, 6).unwrap()
+    }
+
+    struct RomSource {
+        memory: &'static [u8],
+    }
+
+    impl MemorySource for RomSource {
+        fn begin(&mut self, memid: u32, blocks: u8) -> bool {
+            memid == 0x10 && blocks as usize <= self.memory.len().div_ceil(4)
+        }
+
+        fn read(&mut self, _memid: u32, block: u8, data: &mut [u8; 4]) -> usize {
+            let start = block as usize * 4;
+            let chunk = &self.memory[start..self.memory.len().min(start + 4)];
+            data[..chunk.len()].copy_from_slice(chunk);
+            chunk.len()
+        }
+    }
+
+    #[test]
+    fn test_without_source() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DUS, 1, DataType::MEMID(0x10)));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(
+            canas.driver.sent[0].message.message_code,
+            SERVICE_NOT_SUPPORTED
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut source = RomSource {
+            memory: &[1, 2, 3, 4, 5, 6],
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_memory_source(&mut source);
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DUS, 2, DataType::MEMID(0x10)));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.driver.sent.is_empty());
+        for sent in 1..=3 {
+            assert!(canas.dus_streaming());
+            canas.poll_dus().unwrap();
+            assert_eq!(canas.driver.sent.len(), sent);
+        }
+        assert!(!canas.dus_streaming());
+        canas.poll_dus().unwrap();
+
+        let sent = &canas.driver.sent;
+        assert_eq!(sent.len(), 3);
+        assert!(sent
+            .iter()
+            .all(|frame| frame.message_type == MessageType::NSH(129)));
+        assert_eq!(sent[0].message.message_code, 0);
+        assert_eq!(
+            sent[0].message.data_type,
+            DataType::UCHAR4(0, 0, 0, 0).type_id()
+        );
+        assert_eq!(sent[0].message.payload.data, [1, 2, 3, 4]);
+        assert_eq!(sent[1].message.message_code, 1);
+        assert_eq!(sent[1].message.data_type, DataType::UCHAR2(0, 0).type_id());
+        assert_eq!(sent[2].message.message_code, 2);
+        assert_eq!(sent[2].message.data_type, DataType::CHKSUM(0).type_id());
+        assert_eq!(sent[2].message.payload.data, 21u32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_stream_abort() {
+        let mut source = RomSource { memory: &[1, 2] };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_memory_source(&mut source);
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DUS, 1, DataType::MEMID(0x20)));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DUS, 1, DataType::ULONG(0x10)));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DUS, 0, DataType::MEMID(0x10)));
+        for _ in 0..3 {
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert!(canas.driver.sent.iter().all(|frame| {
+            frame.message.message_code == DUS_ABORT
+                && frame.message.data_type == DataType::NODATA.type_id()
+        }));
+    }
+
+    #[test]
+    fn test_stream_retry() {
+        let mut source = RomSource {
+            memory: &[1, 2, 3, 4, 5],
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_memory_source(&mut source);
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::DUS, 2, DataType::MEMID(0x10)));
+        canas.notify_receive_event().unwrap();
+        canas.poll_dus().unwrap();
+        canas.driver.fail_send = true;
+        assert!(canas.poll_dus().is_err());
+        assert_eq!(canas.driver.sent.len(), 1);
+
+        canas.driver.fail_send = false;
+        canas.poll_dus().unwrap();
+        canas.poll_dus().unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert_eq!(canas.driver.sent[1].message.message_code, 1);
+        assert_eq!(canas.driver.sent[1].message.payload.data[0], 5);
+        assert_eq!(canas.driver.sent[0].message.message_code, 0);
+        assert_eq!(
+            canas.driver.sent[2].message.payload.data,
+            15u32.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn test_upload() {
+        let mut source = RomSource {
+            memory: &[1, 2, 3, 4, 5, 6, 7],
+        };
+        let mut server = CANAerospaceLite::new(10, MockDriver::new());
+        server.set_memory_source(&mut source);
+
+        let mut buffer = [0u8; 8];
+        let mut client = CANAerospaceLite::new(20, MockDriver::new());
+        assert_eq!(client.upload_status(), UploadStatus::Idle);
+        client
+            .request_upload(10, channel(), 0x10, 2, &mut buffer)
+            .unwrap();
+        assert_eq!(client.upload_status(), UploadStatus::InProgress);
+        let request = client.driver.sent[0].clone();
+        assert_eq!(request.message.node_id, 10);
+        assert_eq!(request.message.message_code, 2);
+
+        server.driver.queue(request);
+        server.notify_receive_event().unwrap();
+        while server.dus_streaming() {
+            server.poll_dus().unwrap();
+        }
+        for frame in server.driver.sent.iter() {
+            client.driver.queue(frame.clone());
+        }
+        for _ in 0..3 {
+            client.notify_receive_event().unwrap();
+        }
+        assert!(client.read_message().is_none());
+        assert_eq!(client.upload_status(), UploadStatus::Complete(7));
+        let buffer = client.release_upload().unwrap();
+        assert_eq!(buffer[..7], [1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    fn upload_response(message_code: u8, data: DataType) -> CANAerospaceFrame {
+        CANAerospaceFrame {
+            message_type: MessageType::NSH(141),
+            message: RawMessage {
+                node_id: 10,
+                data_type: data.type_id(),
+                service_code: ServiceCodeEnum::DUS.as_u8(),
+                message_code,
+                payload: Payload::from(&data),
+            },
+        }
+    }
+
+    fn upload(responses: &[CANAerospaceFrame], buffer: &mut [u8]) -> UploadStatus {
+        let mut client = CANAerospaceLite::new(20, MockDriver::new());
+        client
+            .request_upload(10, channel(), 0x10, 1, buffer)
+            .unwrap();
+        for response in responses {
+            client.driver.queue(response.clone());
+            client.notify_receive_event().unwrap();
+        }
+        client.upload_status()
+    }
+
+    #[test]
+    fn test_upload_errors() {
+        let mut buffer = [0u8; 4];
+        assert_eq!(
+            upload(&[upload_response(DUS_ABORT, DataType::NODATA)], &mut buffer),
+            UploadStatus::Failed(UploadError::Aborted)
+        );
+        assert_eq!(
+            upload(&[upload_response(1, DataType::UCHAR(1))], &mut buffer),
+            UploadStatus::Failed(UploadError::Sequence)
+        );
+        assert_eq!(
+            upload(
+                &[
+                    upload_response(0, DataType::UCHAR(1)),
+                    upload_response(1, DataType::UCHAR(1)),
+                ],
+                &mut buffer
+            ),
+            UploadStatus::Failed(UploadError::Sequence)
+        );
+        assert_eq!(
+            upload(
+                &[
+                    upload_response(0, DataType::UCHAR2(1, 2)),
+                    upload_response(1, DataType::CHKSUM(4)),
+                ],
+                &mut buffer
+            ),
+            UploadStatus::Failed(UploadError::Checksum)
+        );
+        assert_eq!(
+            upload(
+                &[upload_response(0, DataType::UCHAR4(1, 2, 3, 4))],
+                &mut buffer[..2]
+            ),
+            UploadStatus::Failed(UploadError::BufferTooSmall)
+        );
+        assert_eq!(
+            upload(&[upload_response(0, DataType::ULONG(1))], &mut buffer),
+            UploadStatus::Failed(UploadError::Sequence)
+        );
+    }
+
+    #[test]
+    fn test_early_checksum() {
+        let mut buffer = [0u8; 8];
+        let mut client = CANAerospaceLite::new(20, MockDriver::new());
+        client
+            .request_upload(10, channel(), 0x10, 2, &mut buffer)
+            .unwrap();
+        for response in [
+            upload_response(0, DataType::UCHAR4(1, 2, 3, 4)),
+            upload_response(1, DataType::CHKSUM(10)),
+        ] {
+            client.driver.queue(response);
+            client.notify_receive_event().unwrap();
+        }
+        assert_eq!(
+            client.upload_status(),
+            UploadStatus::Failed(UploadError::Sequence)
+        );
+        let buffer = client.release_upload().unwrap();
+        assert_eq!(buffer[4..], [0; 4]);
+    }
+
+    #[test]
+    fn test_unrelated_responses_are_queued() {
+        let mut buffer = [0u8; 4];
+        let mut client = CANAerospaceLite::new(20, MockDriver::new());
+        client.driver.queue(upload_response(0, DataType::UCHAR(1)));
+        client.notify_receive_event().unwrap();
+        client
+            .request_upload(10, channel(), 0x10, 1, &mut buffer)
+            .unwrap();
+        let mut other_node = upload_response(0, DataType::UCHAR(1));
+        other_node.message.node_id = 11;
+        client.driver.queue(other_node);
+        client.notify_receive_event().unwrap();
+
+        assert!(client.read_message().is_some());
+        assert!(client.read_message().is_some());
+        assert_eq!(client.upload_status(), UploadStatus::InProgress);
+        assert!(client.release_upload().is_some());
+        assert_eq!(client.upload_status(), UploadStatus::Idle);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod acceptancefilter {
+    use crate::{
+        error::Error,
+        filter::{AcceptanceFilter, MessageClass, DROPPED_NODES_SIZE, FILTER_TABLE_SIZE},
+        message::{CANAerospaceFrame, Payload, RawMessage},
+        tests::mock::MockDriver,
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    fn frame(message_type: MessageType, node_id: u8) -> CANAerospaceFrame {
+        CANAerospaceFrame {
+            message_type,
+            message: RawMessage {
+                node_id,
+                data_type: DataType::NODATA.type_id(),
+                service_code: ServiceCodeEnum::IDS.as_u8(),
+                message_code: 0,
+                payload: Payload::from([]),
+            },
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let range = AcceptanceFilter::Range {
+            first: 300,
+            last: 310,
+        };
+        assert!(range.matches(300));
+        assert!(range.matches(310));
+        assert!(!range.matches(311));
+        let mask = AcceptanceFilter::Mask {
+            id: 0x130,
+            mask: 0x7F0,
+        };
+        assert!(mask.matches(0x130));
+        assert!(!mask.matches(0x140));
+        let class = AcceptanceFilter::Class(MessageClass::UDL);
+        assert!(class.matches(1800));
+        assert!(class.matches(1899));
+        assert!(!class.matches(1900));
+    }
+
+    #[test]
+    fn test_add_invalid_and_full() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.add_acceptance_filter(AcceptanceFilter::Range {
+                first: 310,
+                last: 300
+            }),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            canas.add_acceptance_filter(AcceptanceFilter::Mask {
+                id: 0x800,
+                mask: 0x7FF
+            }),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            canas.add_acceptance_filter(AcceptanceFilter::Mask {
+                id: 0x130,
+                mask: 0xFFFF
+            }),
+            Err(Error::InvalidIdentifier)
+        );
+        assert!(canas.acceptance_filters().is_empty());
+        for i in 0..FILTER_TABLE_SIZE as u16 {
+            canas
+                .add_acceptance_filter(AcceptanceFilter::Range { first: i, last: i })
+                .unwrap();
+        }
+        // adding an existing filter is not an error
+        canas
+            .add_acceptance_filter(AcceptanceFilter::Range { first: 0, last: 0 })
+            .unwrap();
+        assert_eq!(
+            canas.add_acceptance_filter(AcceptanceFilter::Class(MessageClass::NOD)),
+            Err(Error::TableFull)
+        );
+        assert_eq!(canas.acceptance_filters().len(), FILTER_TABLE_SIZE);
+
+        for i in 0..DROPPED_NODES_SIZE as u8 {
+            canas.drop_node(i).unwrap();
+        }
+        assert_eq!(canas.drop_node(0xFF), Err(Error::TableFull));
+    }
+
+    #[test]
+    fn test_filtering() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .add_acceptance_filter(AcceptanceFilter::Class(MessageClass::NOD))
+            .unwrap();
+        canas.drop_node(0x20).unwrap();
+        canas.driver.queue(frame(MessageType::NOD(300), 0x21));
+        canas.driver.queue(frame(MessageType::UDH(200), 0x21));
+        canas.driver.queue(frame(MessageType::NOD(301), 0x20));
+        // service requests are never filtered
+        canas.driver.queue(frame(MessageType::NSH(128), 10));
+        for _ in 0..4 {
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.driver.sent.len(), 1);
+        assert_eq!(
+            canas.read_message().unwrap().message_type,
+            MessageType::NOD(300)
+        );
+        assert!(canas.read_message().is_none());
+        assert_eq!(canas.statistics().frames_filtered, 2);
+
+        canas.clear_filters();
+        canas.driver.queue(frame(MessageType::UDH(200), 0x20));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.read_message().is_some());
+    }
+}
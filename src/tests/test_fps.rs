@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod flashprogramming {
+    use crate::{
+        service::{
+            dds::{MemoryTarget, DDS_ABORT, DDS_XOFF, DDS_XON},
+            fps::{FlashProgrammer, FPS_INVALID_SECURITY_CODE, FPS_OK},
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{receive, request, response_code, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    const SECURITY_CODE: u8 = 0x5A;
+
+    struct RamFlash {
+        memory: [u8; 16],
+        erased: bool,
+        corrupt: bool,
+        finished: bool,
+    }
+
+    impl RamFlash {
+        fn new() -> Self {
+            Self {
+                memory: [0; 16],
+                erased: false,
+                corrupt: false,
+                finished: false,
+            }
+        }
+    }
+
+    impl FlashProgrammer for RamFlash {
+        fn erase(&mut self, memid: u32) -> bool {
+            if memid != 1 {
+                return false;
+            }
+            self.memory = [0xFF; 16];
+            self.erased = true;
+            true
+        }
+
+        fn program(&mut self, _memid: u32, offset: u32, data: &[u8]) -> bool {
+            let offset = offset as usize;
+            match self.memory.get_mut(offset..offset + data.len()) {
+                Some(area) => {
+                    area.copy_from_slice(data);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn verify(&mut self, _memid: u32, len: u32) -> bool {
+            !self.corrupt && len as usize <= self.memory.len()
+        }
+
+        fn finish(&mut self) {
+            self.finished = true;
+        }
+    }
+
+    struct NoTarget;
+
+    impl MemoryTarget for NoTarget {
+        fn begin(&mut self, _memid: u32, _blocks: u8) -> bool {
+            false
+        }
+        fn write(&mut self, _memid: u32, _block: u8, _data: &[u8]) -> bool {
+            false
+        }
+        fn commit(&mut self, _memid: u32) -> bool {
+            false
+        }
+        fn abort(&mut self, _memid: u32) {}
+    }
+
+    #[test]
+    fn test_without_programmer() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA),
+        );
+        assert_eq!(code, SERVICE_NOT_SUPPORTED);
+    }
+
+    #[test]
+    fn test_security_code() {
+        let mut flash = RamFlash::new();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_flash_programmer(&mut flash, SECURITY_CODE);
+
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::FPS, 0x00, DataType::NODATA),
+        );
+        assert_eq!(code, FPS_INVALID_SECURITY_CODE);
+        assert!(!canas.is_flash_programming());
+
+        // broadcasts never enter the flash programming state
+        let mut broadcast = request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA);
+        broadcast.message.node_id = 0;
+        canas.driver.queue(broadcast);
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+        assert!(!canas.is_flash_programming());
+
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA),
+        );
+        assert_eq!(code, FPS_OK);
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NSH(129));
+        assert!(canas.is_flash_programming());
+    }
+
+    #[test]
+    fn test_program() {
+        let mut flash = RamFlash::new();
+        let mut memory = NoTarget;
+        {
+            let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+            canas.set_memory_target(&mut memory);
+            canas.set_flash_programmer(&mut flash, SECURITY_CODE);
+
+            // DDS goes to the memory target outside of the flash programming state
+            let code = response_code(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(1)),
+            );
+            assert_eq!(code, DDS_ABORT);
+
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA),
+            );
+            let code = response_code(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::MEMID(1)),
+            );
+            assert_eq!(code, DDS_XON);
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 0, DataType::UCHAR4(1, 2, 3, 4)),
+            );
+            receive(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 1, DataType::UCHAR2(5, 6)),
+            );
+            let code = response_code(
+                &mut canas,
+                request(10, ServiceCodeEnum::DDS, 2, DataType::CHKSUM(21)),
+            );
+            assert_eq!(code, DDS_XOFF);
+
+            canas.leave_flash_programming();
+            assert!(!canas.is_flash_programming());
+        }
+        assert!(flash.erased);
+        assert_eq!(flash.memory[..7], [1, 2, 3, 4, 5, 6, 0xFF]);
+        assert!(flash.finished);
+    }
+
+    #[test]
+    fn test_verify_failed() {
+        let mut flash = RamFlash::new();
+        flash.corrupt = true;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_flash_programmer(&mut flash, SECURITY_CODE);
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA),
+        );
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 1, DataType::MEMID(1)),
+        );
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 0, DataType::UCHAR(1)),
+        );
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 1, DataType::CHKSUM(1)),
+        );
+        assert_eq!(code, DDS_ABORT);
+    }
+
+    #[test]
+    fn test_erase_failed() {
+        let mut flash = RamFlash::new();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_flash_programmer(&mut flash, SECURITY_CODE);
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA),
+        );
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 1, DataType::MEMID(2)),
+        );
+        assert_eq!(code, DDS_ABORT);
+    }
+
+    #[test]
+    fn test_leave_aborts_transfer() {
+        let mut flash = RamFlash::new();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_flash_programmer(&mut flash, SECURITY_CODE);
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::FPS, SECURITY_CODE, DataType::NODATA),
+        );
+        receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 1, DataType::MEMID(1)),
+        );
+        assert!(canas.dds_in_progress());
+        canas.leave_flash_programming();
+        assert!(!canas.dds_in_progress());
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::DDS, 0, DataType::UCHAR(1)),
+        );
+        assert_eq!(code, SERVICE_NOT_SUPPORTED);
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod filtersetting {
+    use crate::{
+        filter::{AcceptanceFilter, FILTER_TABLE_SIZE},
+        service::fss::{
+            FSS_ADD_MASK, FSS_ADD_RANGE, FSS_CLEAR, FSS_DROP_NODE, FSS_INVALID, FSS_OK,
+            FSS_TABLE_FULL,
+        },
+        tests::mock::{receive, request, response_code, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_remote_filters() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let code = response_code(
+            &mut canas,
+            request(
+                10,
+                ServiceCodeEnum::FSS,
+                FSS_ADD_RANGE,
+                DataType::USHORT2(300, 399),
+            ),
+        );
+        assert_eq!(code, FSS_OK);
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NSH(129));
+        let code = response_code(
+            &mut canas,
+            request(
+                10,
+                ServiceCodeEnum::FSS,
+                FSS_ADD_MASK,
+                DataType::USHORT2(0x700, 0x7F0),
+            ),
+        );
+        assert_eq!(code, FSS_OK);
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::FSS, FSS_DROP_NODE, DataType::UCHAR(3)),
+        );
+        assert_eq!(code, FSS_OK);
+        assert_eq!(
+            canas.acceptance_filters(),
+            [
+                AcceptanceFilter::Range {
+                    first: 300,
+                    last: 399
+                },
+                AcceptanceFilter::Mask {
+                    id: 0x700,
+                    mask: 0x7F0
+                }
+            ]
+        );
+
+        // broadcasts are executed without response
+        canas.driver.queue(request(
+            0,
+            ServiceCodeEnum::FSS,
+            FSS_CLEAR,
+            DataType::NODATA,
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert!(canas.acceptance_filters().is_empty());
+    }
+
+    #[test]
+    fn test_remote_errors() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let code = response_code(
+            &mut canas,
+            request(
+                10,
+                ServiceCodeEnum::FSS,
+                FSS_ADD_RANGE,
+                DataType::USHORT2(399, 300),
+            ),
+        );
+        assert_eq!(code, FSS_INVALID);
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::FSS, FSS_ADD_MASK, DataType::ULONG(0)),
+        );
+        assert_eq!(code, FSS_INVALID);
+        let code = response_code(
+            &mut canas,
+            request(10, ServiceCodeEnum::FSS, 9, DataType::NODATA),
+        );
+        assert_eq!(code, FSS_INVALID);
+
+        for i in 0..FILTER_TABLE_SIZE as u16 {
+            receive(
+                &mut canas,
+                request(
+                    10,
+                    ServiceCodeEnum::FSS,
+                    FSS_ADD_RANGE,
+                    DataType::USHORT2(i, i),
+                ),
+            );
+        }
+        let code = response_code(
+            &mut canas,
+            request(
+                10,
+                ServiceCodeEnum::FSS,
+                FSS_ADD_RANGE,
+                DataType::USHORT2(300, 300),
+            ),
+        );
+        assert_eq!(code, FSS_TABLE_FULL);
+    }
+}
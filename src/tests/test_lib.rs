@@ -2,47 +2,22 @@
 pub mod canaerospacelite {
 
     use crate::{
-        driver::CANAerospaceDriver,
+        error::Error,
         message::{CANAerospaceFrame, CANAerospaceMessage, Payload, RawMessage},
+        service::SERVICE_NOT_SUPPORTED,
+        tests::mock::{MockDriver, MockError},
         types::{
-            DataType, HardwareRevision, IDSConfiguration, MessageType, ServiceCodeEnum,
-            SoftwareRevision,
+            ChannelPriority, DataType, HardwareRevision, IDSConfiguration, MessageType,
+            ServiceChannel, ServiceCodeEnum, SoftwareRevision,
         },
-        CANAerospaceLite, IDS_CONF_STANDARD, IDS_MSG_HEADER_STANDARD,
+        CANAerospaceLite, IDS_CONF_STANDARD, IDS_MSG_HEADER_STANDARD, MESSAGE_CODE_TABLE_SIZE,
     };
 
-    static mut DRIVER_SEND_FRAME_CALL: u8 = 0;
-    static mut DRIVER_RECV_FRAME_CALL: u8 = 0;
-    static mut DRIVER_FRAME_HOLDER: Option<CANAerospaceFrame> = None;
-
-    struct CANDriverMock;
-    impl CANAerospaceDriver for CANDriverMock {
-        #[cfg(not(tarpaulin_include))]
-        fn send_frame(&mut self, frame: CANAerospaceFrame) {
-            unsafe {
-                DRIVER_SEND_FRAME_CALL += 1;
-                DRIVER_FRAME_HOLDER = Some(frame);
-            }
-        }
-
-        #[cfg(not(tarpaulin_include))]
-        fn recv_frame(&mut self) -> Option<CANAerospaceFrame> {
-            unsafe {
-                DRIVER_RECV_FRAME_CALL += 1;
-                if let Some(frame) = &DRIVER_FRAME_HOLDER {
-                    Some(frame.clone())
-                } else {
-                    None
-                }
-            }
-        }
-    }
-
     #[test]
     fn test_new() {
-        let canas = CANAerospaceLite::new(10, CANDriverMock {});
-        assert_eq!(10, canas.node_id);
-        assert_eq!(0, canas.nod_count);
+        let canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(10, canas.node_id());
+        assert_eq!(0, canas.message_codes.len());
         assert_eq!(canas.identification.hw_rev.0, 0);
         assert_eq!(canas.identification.sw_rev.0, 0);
         assert_eq!(canas.identification.configuration.0, 0);
@@ -52,7 +27,7 @@ pub mod canaerospacelite {
 
     #[test]
     fn test_set_hw_revision() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
         canas.set_hw_revision(HardwareRevision(0x01));
         assert_eq!(canas.identification.hw_rev.0, 0x01);
         canas.set_hw_revision(HardwareRevision(0x00));
@@ -63,7 +38,7 @@ pub mod canaerospacelite {
 
     #[test]
     fn test_set_sw_revision() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
         canas.set_sw_revision(SoftwareRevision(0x01));
         assert_eq!(canas.identification.sw_rev.0, 0x01);
         canas.set_sw_revision(SoftwareRevision(0x00));
@@ -74,18 +49,25 @@ pub mod canaerospacelite {
 
     #[test]
     fn test_set_ids_configuration() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
-        canas.set_ids_configuration(IDSConfiguration(0x00));
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_ids_configuration(IDSConfiguration(0x00)).unwrap();
         assert_eq!(canas.identification.configuration.0, 0);
         assert_eq!(canas.identification.configuration.0, IDS_CONF_STANDARD.0);
 
-        canas.set_ids_configuration(IDSConfiguration(0xFF));
+        assert_eq!(
+            canas.set_ids_configuration(IDSConfiguration(0xFF)),
+            Err(Error::InvalidDistribution)
+        );
+        canas
+            .register_id_distribution(IDSConfiguration(0xFF), &[])
+            .unwrap();
+        canas.set_ids_configuration(IDSConfiguration(0xFF)).unwrap();
         assert_eq!(canas.identification.configuration.0, 0xFF);
     }
 
     #[test]
     fn test_set_message_header_conf() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
         canas.set_message_header_conf(0x00);
         assert_eq!(canas.identification.header, 0);
         assert_eq!(canas.identification.header, IDS_MSG_HEADER_STANDARD);
@@ -96,7 +78,7 @@ pub mod canaerospacelite {
 
     #[test]
     fn test_send_message_nod() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
         let message = CANAerospaceMessage {
             message_type: MessageType::NOD(300),
             node_id: 10,
@@ -104,60 +86,239 @@ pub mod canaerospacelite {
             message_code: 0,
             data: DataType::USHORT2(0xDEAD, 0xBEEF),
         };
-        unsafe {
-            DRIVER_SEND_FRAME_CALL = 0;
-            canas.send_message(message);
-
-            assert_eq!(DRIVER_SEND_FRAME_CALL, 1);
-            assert!(matches!(DRIVER_FRAME_HOLDER, Some(_)));
-            if let Some(frame) = &DRIVER_FRAME_HOLDER {
-                assert!(matches!(frame.message_type, MessageType::NOD(300)));
-                assert_eq!(frame.message.node_id, 10);
-                assert_eq!(frame.message.service_code, ServiceCodeEnum::UNKNOWN.as_u8());
-                assert_eq!(frame.message.message_code, canas.nod_count);
-
-                let data = DataType::USHORT2(0xDEAD, 0xBEEF).to_be_bytes();
-                for (i, val) in data.iter().enumerate() {
-                    assert_eq!(frame.message.payload.data[i], *val);
-                }
-            }
+        canas.send_message(message).unwrap();
+
+        assert_eq!(canas.driver.sent.len(), 1);
+        let frame = &canas.driver.sent[0];
+        assert!(matches!(frame.message_type, MessageType::NOD(300)));
+        assert_eq!(frame.message.node_id, 10);
+        assert_eq!(frame.message.service_code, ServiceCodeEnum::UNKNOWN.as_u8());
+        assert_eq!(frame.message.message_code, 0);
+
+        let data = DataType::USHORT2(0xDEAD, 0xBEEF).to_be_bytes();
+        for (i, val) in data.iter().enumerate() {
+            assert_eq!(frame.message.payload.data[i], *val);
+        }
+    }
+
+    #[test]
+    fn test_send_message_code_sequence() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for _ in 0..3 {
+            canas
+                .send_message(CANAerospaceMessage::new(
+                    MessageType::NOD(300),
+                    10,
+                    0,
+                    0xAA,
+                    DataType::FLOAT(1.0),
+                ))
+                .unwrap();
+        }
+        canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::UDL(1800),
+                10,
+                0,
+                0xAA,
+                DataType::FLOAT(1.0),
+            ))
+            .unwrap();
+        let codes: heapless::Vec<u8, 4> = canas
+            .driver
+            .sent
+            .iter()
+            .map(|f| f.message.message_code)
+            .collect();
+        assert_eq!(codes, [0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_send_message_code_wraps() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for _ in 0..=255 {
+            canas
+                .send_message(CANAerospaceMessage::new(
+                    MessageType::UDH(200),
+                    10,
+                    0,
+                    0,
+                    DataType::NODATA,
+                ))
+                .unwrap();
+            canas.driver.sent.clear();
         }
+        canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::UDH(200),
+                10,
+                0,
+                0,
+                DataType::NODATA,
+            ))
+            .unwrap();
+        assert_eq!(canas.driver.sent[0].message.message_code, 0);
+    }
+
+    #[test]
+    fn test_send_message_code_service_untouched() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for _ in 0..2 {
+            canas
+                .send_message(CANAerospaceMessage::new(
+                    MessageType::NSL(2001),
+                    10,
+                    0,
+                    0x42,
+                    DataType::NODATA,
+                ))
+                .unwrap();
+        }
+        assert_eq!(canas.driver.sent[0].message.message_code, 0x42);
+        assert_eq!(canas.driver.sent[1].message.message_code, 0x42);
+        assert_eq!(canas.message_codes.len(), 0);
+    }
+
+    #[test]
+    fn test_send_message_code_table_full() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for i in 0..MESSAGE_CODE_TABLE_SIZE as u16 {
+            canas
+                .send_message(CANAerospaceMessage::new(
+                    MessageType::NOD(300 + i),
+                    10,
+                    0,
+                    0x42,
+                    DataType::NODATA,
+                ))
+                .unwrap();
+        }
+        canas.driver.sent.clear();
+        canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::NOD(1000),
+                10,
+                0,
+                0x42,
+                DataType::NODATA,
+            ))
+            .unwrap();
+        assert_eq!(canas.driver.sent[0].message.message_code, 0x42);
+    }
+
+    #[test]
+    fn test_send_message_node_id_stamped() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let types = [
+            MessageType::EED(0),
+            MessageType::NOD(300),
+            MessageType::UDH(200),
+            MessageType::UDL(1800),
+            MessageType::DSD(1900),
+        ];
+        for message_type in types.iter() {
+            canas
+                .send_message(CANAerospaceMessage::new(
+                    *message_type,
+                    0,
+                    0,
+                    0,
+                    DataType::NODATA,
+                ))
+                .unwrap();
+        }
+        assert_eq!(canas.driver.sent.len(), types.len());
+        for frame in canas.driver.sent.iter() {
+            assert_eq!(frame.message.node_id, 10);
+        }
+    }
+
+    #[test]
+    fn test_send_message_node_id_service_target() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::NSH(128),
+                20,
+                0,
+                0,
+                DataType::NODATA,
+            ))
+            .unwrap();
+        canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::NSL(2000),
+                0,
+                0,
+                0,
+                DataType::NODATA,
+            ))
+            .unwrap();
+        assert_eq!(canas.driver.sent[0].message.node_id, 20);
+        assert_eq!(canas.driver.sent[1].message.node_id, 0);
+    }
+
+    #[test]
+    fn test_send_message_node_id_stamping_disabled() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_node_id_stamping(false);
+        canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::NOD(300),
+                42,
+                0,
+                0,
+                DataType::NODATA,
+            ))
+            .unwrap();
+        assert_eq!(canas.driver.sent[0].message.node_id, 42);
+    }
+
+    #[test]
+    fn test_send_message_driver_error() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.fail_send = true;
+        let result = canas.send_message(CANAerospaceMessage::new(
+            MessageType::NOD(300),
+            10,
+            0,
+            0,
+            DataType::NODATA,
+        ));
+        assert_eq!(result, Err(Error::Driver(MockError)));
     }
 
     // TODO: test_send_message for each message type
 
     #[test]
     fn test_read_message_empty() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
-        assert_eq!(canas.read_message().is_none(), true);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert!(canas.read_message().is_none());
     }
 
     #[test]
     fn test_read_message_nod() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
-        unsafe {
-            DRIVER_FRAME_HOLDER = Some(CANAerospaceFrame {
-                message_type: MessageType::NOD(300),
-                message: RawMessage {
-                    node_id: 0,
-                    data_type: DataType::ULONG(0).type_id(),
-                    service_code: 0xFF,
-                    message_code: 0,
-                    payload: Payload::from(0xDEAD_BEEFu32.to_be_bytes()),
-                },
-            });
-            DRIVER_RECV_FRAME_CALL = 0;
-            canas.notify_receive_event();
-            assert_eq!(DRIVER_RECV_FRAME_CALL, 1);
-            let message = canas.read_message();
-            assert!(matches!(message, Some(_)));
-            if let Some(m) = message {
-                assert!(matches!(m.message_type, MessageType::NOD(300)));
-                assert_eq!(m.node_id, 0);
-                assert!(matches!(m.service_code, ServiceCodeEnum::UNKNOWN));
-                assert_eq!(m.message_code, 0);
-                assert!(matches!(m.data, DataType::ULONG(0xDEAD_BEEF)));
-            }
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.queue(CANAerospaceFrame {
+            message_type: MessageType::NOD(300),
+            message: RawMessage {
+                node_id: 0,
+                data_type: DataType::ULONG(0).type_id(),
+                service_code: 0xFF,
+                message_code: 0,
+                payload: Payload::from(0xDEAD_BEEFu32.to_be_bytes()),
+            },
+        });
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.recv_calls, 1);
+        let message = canas.read_message();
+        assert!(message.is_some());
+        if let Some(m) = message {
+            assert!(matches!(m.message_type, MessageType::NOD(300)));
+            assert_eq!(m.node_id, 0);
+            assert!(matches!(m.service_code, ServiceCodeEnum::UNKNOWN));
+            assert_eq!(m.message_code, 0);
+            assert!(matches!(m.data, DataType::ULONG(0xDEAD_BEEF)));
         }
     }
 
@@ -165,89 +326,140 @@ pub mod canaerospacelite {
 
     #[test]
     fn test_notify_receive_event() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
-        unsafe {
-            DRIVER_RECV_FRAME_CALL = 0;
-            canas.notify_receive_event();
-            canas.notify_receive_event();
-            canas.notify_receive_event();
-            assert_eq!(DRIVER_RECV_FRAME_CALL, 3);
-        }
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.recv_calls, 3);
+    }
+
+    #[test]
+    fn test_notify_receive_event_driver_error() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.fail_recv = true;
+        assert_eq!(canas.notify_receive_event(), Err(Error::Driver(MockError)));
+    }
+
+    #[test]
+    fn test_handle_service_request_ids_driver_error() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.fail_send = true;
+        canas.driver.queue(CANAerospaceFrame {
+            message_type: MessageType::NSH(128),
+            message: RawMessage {
+                node_id: 10,
+                data_type: DataType::NODATA.type_id(),
+                service_code: ServiceCodeEnum::IDS.as_u8(),
+                message_code: 0,
+                payload: Payload::from([]),
+            },
+        });
+        assert_eq!(canas.notify_receive_event(), Err(Error::Driver(MockError)));
     }
 
     #[test]
     fn test_handle_service_request_discard() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
-        unsafe {
-            let be_discarded = CANAerospaceFrame {
-                message_type: MessageType::NSH(128),
-                message: RawMessage {
-                    node_id: 15,
-                    data_type: DataType::NODATA.type_id(),
-                    service_code: ServiceCodeEnum::IDS.as_u8(),
-                    message_code: 0,
-                    payload: Payload::from([]),
-                },
-            };
-            DRIVER_SEND_FRAME_CALL = 0;
-            canas.handle_service_request(be_discarded);
-            assert_eq!(DRIVER_SEND_FRAME_CALL, 0);
-            assert_eq!(canas.rx_queue.len(), 0);
-        }
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let be_discarded = CANAerospaceFrame {
+            message_type: MessageType::NSH(128),
+            message: RawMessage {
+                node_id: 15,
+                data_type: DataType::NODATA.type_id(),
+                service_code: ServiceCodeEnum::IDS.as_u8(),
+                message_code: 0,
+                payload: Payload::from([]),
+            },
+        };
+        canas.handle_service_request(be_discarded).unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+        assert_eq!(canas.rx_queue.len(), 0);
     }
 
     #[test]
     fn test_handle_service_request_ids() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
         canas.set_sw_revision(SoftwareRevision(0x01));
-        unsafe {
-            let ids = CANAerospaceFrame {
-                message_type: MessageType::NSH(128),
-                message: RawMessage {
-                    node_id: 10,
-                    data_type: DataType::NODATA.type_id(),
-                    service_code: ServiceCodeEnum::IDS.as_u8(),
-                    message_code: 0,
-                    payload: Payload::from([]),
-                },
-            };
-            DRIVER_SEND_FRAME_CALL = 0;
-            canas.handle_service_request(ids);
-            assert_eq!(DRIVER_SEND_FRAME_CALL, 1);
-            assert!(matches!(DRIVER_FRAME_HOLDER, Some(_)));
-            if let Some(frame) = &DRIVER_FRAME_HOLDER {
-                assert_eq!(frame.message_type.id(), 129);
-                assert_eq!(frame.message.payload.data[0], canas.identification.hw_rev.0);
-                assert_eq!(frame.message.payload.data[1], canas.identification.sw_rev.0);
-                assert_eq!(
-                    frame.message.payload.data[2],
-                    canas.identification.configuration.0
-                );
-                assert_eq!(frame.message.payload.data[3], canas.identification.header);
-            }
-            assert_eq!(canas.rx_queue.len(), 0);
-        }
+        let ids = CANAerospaceFrame {
+            message_type: MessageType::NSH(128),
+            message: RawMessage {
+                node_id: 10,
+                data_type: DataType::NODATA.type_id(),
+                service_code: ServiceCodeEnum::IDS.as_u8(),
+                message_code: 0,
+                payload: Payload::from([]),
+            },
+        };
+        canas.handle_service_request(ids).unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+        let frame = &canas.driver.sent[0];
+        assert_eq!(frame.message_type.id(), 129);
+        assert_eq!(frame.message.payload.data[0], canas.identification.hw_rev.0);
+        assert_eq!(frame.message.payload.data[1], canas.identification.sw_rev.0);
+        assert_eq!(
+            frame.message.payload.data[2],
+            canas.identification.configuration.0
+        );
+        assert_eq!(frame.message.payload.data[3], canas.identification.header);
+        assert_eq!(canas.rx_queue.len(), 0);
     }
 
     #[test]
-    fn test_handle_service_request_notids() {
-        let mut canas = CANAerospaceLite::new(10, CANDriverMock {});
+    fn test_handle_service_request_unsupported() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
         canas.set_sw_revision(SoftwareRevision(0x01));
-        unsafe {
-            let not_ids = CANAerospaceFrame {
-                message_type: MessageType::NSH(128),
-                message: RawMessage {
-                    node_id: 10,
-                    data_type: DataType::NODATA.type_id(),
-                    service_code: ServiceCodeEnum::NSS.as_u8(),
-                    message_code: 0,
-                    payload: Payload::from([]),
-                },
-            };
-            DRIVER_SEND_FRAME_CALL = 0;
-            canas.handle_service_request(not_ids);
-            assert_eq!(DRIVER_SEND_FRAME_CALL, 0);
-            assert_eq!(canas.rx_queue.len(), 1);
-        }
+        let not_ids = CANAerospaceFrame {
+            message_type: MessageType::NSH(128),
+            message: RawMessage {
+                node_id: 10,
+                data_type: DataType::NODATA.type_id(),
+                service_code: ServiceCodeEnum::NSS.as_u8(),
+                message_code: 0,
+                payload: Payload::from([]),
+            },
+        };
+        canas.handle_service_request(not_ids).unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+        let frame = &canas.driver.sent[0];
+        assert_eq!(frame.message_type.id(), 129);
+        assert_eq!(frame.message.node_id, 10);
+        assert_eq!(frame.message.service_code, ServiceCodeEnum::NSS.as_u8());
+        assert_eq!(frame.message.message_code, SERVICE_NOT_SUPPORTED);
+        assert_eq!(frame.message.data_type, DataType::NODATA.type_id());
+        assert_eq!(canas.rx_queue.len(), 0);
+    }
+
+    #[test]
+    fn test_service_channels() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let ids = |id| CANAerospaceFrame {
+            message_type: MessageType::NSH(id),
+            message: RawMessage {
+                node_id: 10,
+                data_type: DataType::NODATA.type_id(),
+                service_code: ServiceCodeEnum::IDS.as_u8(),
+                message_code: 0,
+                payload: Payload::from([]),
+            },
+        };
+        let first = ServiceChannel::new(ChannelPriority::High, 1).unwrap();
+        let other = ServiceChannel::new(ChannelPriority::High, 2).unwrap();
+        canas.set_service_channels(&[first]);
+        assert!(canas.listens_on_channel(first));
+        assert!(!canas.listens_on_channel(other));
+
+        // requests on other channels are queued for the application
+        canas.driver.queue(ids(132));
+        canas.driver.queue(ids(130));
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NSH(131));
+        assert_eq!(canas.rx_queue.len(), 1);
+
+        canas.listen_on_all_channels();
+        canas.driver.queue(ids(132));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NSH(133));
     }
 }
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod moduleconfiguration {
+    use crate::{
+        error::Error,
+        message::CANAerospaceMessage,
+        service::{
+            mcs::{
+                Parameter, MCS_OUT_OF_RANGE, MCS_READ_ONLY, MCS_TYPE_MISMATCH,
+                MCS_UNKNOWN_PARAMETER,
+            },
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{receive, request, Eeprom, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    const GAIN: Parameter = Parameter {
+        value: DataType::USHORT(100),
+        min: Some(DataType::USHORT(10)),
+        max: Some(DataType::USHORT(1000)),
+        read_only: false,
+    };
+
+    #[test]
+    fn test_register() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.register_parameter(3, GAIN).unwrap();
+        assert_eq!(canas.parameter(3), Some(DataType::USHORT(100)));
+        assert_eq!(canas.parameter(4), None);
+
+        let out_of_range = Parameter {
+            value: DataType::USHORT(5),
+            ..GAIN
+        };
+        assert_eq!(
+            canas.register_parameter(4, out_of_range),
+            Err(Error::InvalidParameter)
+        );
+        let mismatch = Parameter {
+            value: DataType::SHORT(100),
+            ..GAIN
+        };
+        assert_eq!(
+            canas.register_parameter(4, mismatch),
+            Err(Error::InvalidParameter)
+        );
+        let unlimited = Parameter {
+            value: DataType::BLONG(0xFF00),
+            min: None,
+            max: None,
+            read_only: true,
+        };
+        canas.register_parameter(4, unlimited).unwrap();
+
+        assert_eq!(
+            canas.set_parameter(3, DataType::USHORT(1001)),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(
+            canas.set_parameter(5, DataType::USHORT(10)),
+            Err(Error::InvalidParameter)
+        );
+        canas.set_parameter(4, DataType::BLONG(1)).unwrap();
+        assert_eq!(canas.parameter(4), Some(DataType::BLONG(1)));
+    }
+
+    #[test]
+    fn test_limits_per_element() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let offsets = Parameter {
+            value: DataType::CHAR2(0, 0),
+            min: Some(DataType::CHAR2(-10, 0)),
+            max: Some(DataType::CHAR2(10, 5)),
+            read_only: false,
+        };
+        canas.register_parameter(0, offsets).unwrap();
+        canas.set_parameter(0, DataType::CHAR2(-10, 5)).unwrap();
+        assert!(canas.set_parameter(0, DataType::CHAR2(-10, -1)).is_err());
+        assert!(canas.set_parameter(0, DataType::CHAR2(11, 0)).is_err());
+    }
+
+    #[test]
+    fn test_nan_out_of_range() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let trim = Parameter {
+            value: DataType::FLOAT(0.0),
+            min: None,
+            max: Some(DataType::FLOAT(1.5)),
+            read_only: false,
+        };
+        canas.register_parameter(1, trim).unwrap();
+        assert!(canas.set_parameter(1, DataType::FLOAT(f32::NAN)).is_err());
+
+        let write = CANAerospaceMessage {
+            message_type: MessageType::NSH(128),
+            node_id: 10,
+            service_code: ServiceCodeEnum::MCS,
+            message_code: 1,
+            data: DataType::FLOAT(f32::NAN),
+        };
+        let response = canas.handle_mcs(&write).unwrap();
+        assert_eq!(response.data, DataType::ERROR(MCS_OUT_OF_RANGE));
+        assert_eq!(canas.parameter(1), Some(DataType::FLOAT(0.0)));
+    }
+
+    #[test]
+    fn test_read_write() {
+        let mut eeprom = Eeprom::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MCS, 3, DataType::NODATA),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, SERVICE_NOT_SUPPORTED);
+
+        canas.set_config_store(&mut eeprom);
+        canas.register_parameter(3, GAIN).unwrap();
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MCS, 3, DataType::NODATA),
+        )
+        .unwrap();
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NSH(129));
+        assert_eq!(response.message_code, 3);
+        assert_eq!(DataType::from(&response), DataType::USHORT(100));
+
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MCS, 3, DataType::USHORT(250)),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, 3);
+        assert_eq!(DataType::from(&response), DataType::USHORT(250));
+        assert_eq!(canas.parameter(3), Some(DataType::USHORT(250)));
+
+        // broadcasted parameters are executed without response
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::MCS, 3, DataType::USHORT(500)));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert_eq!(canas.parameter(3), Some(DataType::USHORT(500)));
+        drop(canas);
+        assert_eq!(
+            eeprom.parameters.as_slice(),
+            &[(3, DataType::USHORT(250)), (3, DataType::USHORT(500))]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.register_parameter(3, GAIN).unwrap();
+        canas
+            .register_parameter(
+                4,
+                Parameter {
+                    read_only: true,
+                    ..GAIN
+                },
+            )
+            .unwrap();
+        let requests = [
+            (5, DataType::NODATA, MCS_UNKNOWN_PARAMETER),
+            (4, DataType::USHORT(200), MCS_READ_ONLY),
+            (3, DataType::USHORT(1001), MCS_OUT_OF_RANGE),
+            (3, DataType::USHORT(9), MCS_OUT_OF_RANGE),
+            (3, DataType::ULONG(200), MCS_TYPE_MISMATCH),
+        ];
+        for (index, data, code) in requests {
+            let response =
+                receive(&mut canas, request(10, ServiceCodeEnum::MCS, index, data)).unwrap();
+            assert_eq!(response.message_code, index);
+            assert_eq!(DataType::from(&response), DataType::ERROR(code));
+        }
+        assert_eq!(canas.parameter(3), Some(DataType::USHORT(100)));
+        assert_eq!(canas.parameter(4), Some(DataType::USHORT(100)));
+    }
+}
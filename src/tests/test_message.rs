@@ -107,10 +107,10 @@ mod canaerospaceframe {
             message_type: MessageType::UDH(297),
             message: RawMessage::empty(),
         };
-        assert_eq!(frame_1.eq(&frame_2), false);
-        assert_eq!(frame_2.eq(&frame_1), false);
-        assert_eq!(frame_1.eq(&frame_3), true);
-        assert_eq!(frame_3.eq(&frame_1), true);
+        assert!(!frame_1.eq(&frame_2));
+        assert!(!frame_2.eq(&frame_1));
+        assert!(frame_1.eq(&frame_3));
+        assert!(frame_3.eq(&frame_1));
     }
 
     #[test]
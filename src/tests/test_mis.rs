@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod moduleinformation {
+    use crate::{
+        error::Error,
+        service::{
+            mis::{
+                ModuleInfo, ModuleKind, MIS_INVALID, MIS_ITEM_PART_NUMBER, MIS_ITEM_SERIAL_NUMBER,
+                MODULE_TABLE_SIZE,
+            },
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{receive, request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    const SENSOR: ModuleInfo = ModuleInfo {
+        kind: ModuleKind::Sensor,
+        revision: 3,
+        part_number: 4711,
+        serial_number: 0x0134_0A2A,
+    };
+
+    #[test]
+    fn test_register() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for index in 0..MODULE_TABLE_SIZE {
+            assert_eq!(canas.register_module(SENSOR), Ok(index as u8));
+        }
+        assert_eq!(canas.register_module(SENSOR), Err(Error::TableFull));
+        assert_eq!(canas.modules().len(), MODULE_TABLE_SIZE);
+    }
+
+    #[test]
+    fn test_items() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MIS, 0, DataType::NODATA),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, SERVICE_NOT_SUPPORTED);
+
+        canas
+            .register_module(ModuleInfo {
+                kind: ModuleKind::Software,
+                revision: 1,
+                part_number: 1,
+                serial_number: 1,
+            })
+            .unwrap();
+        canas.register_module(SENSOR).unwrap();
+
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MIS, 1, DataType::NODATA),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, 1);
+        assert_eq!(DataType::from(&response), DataType::UCHAR2(2, 3));
+        let response = receive(
+            &mut canas,
+            request(
+                10,
+                ServiceCodeEnum::MIS,
+                1,
+                DataType::UCHAR(MIS_ITEM_PART_NUMBER),
+            ),
+        )
+        .unwrap();
+        assert_eq!(DataType::from(&response), DataType::ULONG(4711));
+        let response = receive(
+            &mut canas,
+            request(
+                10,
+                ServiceCodeEnum::MIS,
+                1,
+                DataType::UCHAR(MIS_ITEM_SERIAL_NUMBER),
+            ),
+        )
+        .unwrap();
+        assert_eq!(DataType::from(&response), DataType::ULONG(0x0134_0A2A));
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NSH(129));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.register_module(SENSOR).unwrap();
+
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MIS, 1, DataType::NODATA),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, MIS_INVALID);
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MIS, 0, DataType::UCHAR(3)),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, MIS_INVALID);
+        let response = receive(
+            &mut canas,
+            request(10, ServiceCodeEnum::MIS, 0, DataType::ULONG(0)),
+        )
+        .unwrap();
+        assert_eq!(response.message_code, MIS_INVALID);
+        assert_eq!(response.data_type, DataType::NODATA.type_id());
+
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::MIS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod nodeidsetting {
+    use crate::{
+        error::Error,
+        service::nis::{NIS_INVALID, NIS_OK},
+        tests::mock::{request, Eeprom, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_set_node_id() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(canas.set_node_id(0), Err(Error::InvalidNodeId));
+        assert_eq!(canas.node_id(), 10);
+        canas.set_node_id(20).unwrap();
+        assert_eq!(canas.node_id(), 20);
+    }
+
+    #[test]
+    fn test_transfers_aborted() {
+        let mut source = || DataType::ULONG(1);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 1000, &mut source)
+            .unwrap();
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::STS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        canas.set_node_id(10).unwrap();
+        assert!(canas.sts_transmitting());
+        canas.set_node_id(20).unwrap();
+        assert!(!canas.sts_transmitting());
+    }
+
+    #[test]
+    fn test_switch_after_response() {
+        let mut eeprom = Eeprom::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_config_store(&mut eeprom);
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NIS, 0, DataType::UCHAR(42)));
+        canas.notify_receive_event().unwrap();
+        let response = &canas.driver.sent[0];
+        assert_eq!(response.message_type, MessageType::NSH(129));
+        assert_eq!(response.message.node_id, 10);
+        assert_eq!(response.message.message_code, NIS_OK);
+        assert_eq!(canas.node_id(), 42);
+
+        // requests to the old ID are not accepted anymore, the new ID answers
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::IDS, 0, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(42, ServiceCodeEnum::IDS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        let response = &canas.driver.sent[1];
+        assert_eq!(response.message.service_code, ServiceCodeEnum::IDS.as_u8());
+        assert_eq!(response.message.node_id, 42);
+        drop(canas);
+        assert_eq!(eeprom.node_id, Some(42));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut eeprom = Eeprom::default();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_config_store(&mut eeprom);
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NIS, 0, DataType::UCHAR(0)));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NIS, 0, DataType::USHORT(42)));
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::NIS, 0, DataType::UCHAR(42)));
+        for _ in 0..3 {
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert!(canas
+            .driver
+            .sent
+            .iter()
+            .all(|response| response.message.message_code == NIS_INVALID));
+        assert_eq!(canas.node_id(), 10);
+        drop(canas);
+        assert_eq!(eeprom.node_id, None);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod nodesynchronisation {
+    use core::cell::Cell;
+
+    use crate::{
+        service::{
+            nss::{TimeBase, NSS_INVALID_TIMESTAMP, NSS_OK},
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum, Timestamp},
+        CANAerospaceLite,
+    };
+
+    struct Clock {
+        now: Cell<Timestamp>,
+    }
+
+    impl TimeBase for Clock {
+        fn now(&self) -> Timestamp {
+            self.now.get()
+        }
+    }
+
+    #[test]
+    fn test_without_time_base() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NSS, 0, DataType::ULONG(1000)));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(
+            canas.driver.sent[0].message.message_code,
+            SERVICE_NOT_SUPPORTED
+        );
+        assert_eq!(canas.clock_offset(), None);
+        assert_eq!(canas.synchronised_time(), None);
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let clock = Clock {
+            now: Cell::new(400),
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_time_base(&clock);
+        assert_eq!(canas.synchronised_time(), None);
+
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::NSS, 0, DataType::ULONG(1000)));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+        assert_eq!(canas.clock_offset(), Some(600));
+
+        clock.now.set(500);
+        assert_eq!(canas.synchronised_time(), Some(1100));
+        assert!(canas.read_message().is_none());
+    }
+
+    #[test]
+    fn test_negative_offset_and_wrap() {
+        let clock = Clock {
+            now: Cell::new(1000),
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_time_base(&clock);
+        canas.driver.queue(request(
+            0,
+            ServiceCodeEnum::NSS,
+            0,
+            DataType::ULONG(u32::MAX - 99),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.clock_offset(), Some(-1100));
+        clock.now.set(1200);
+        assert_eq!(canas.synchronised_time(), Some(100));
+    }
+
+    #[test]
+    fn test_addressed() {
+        let clock = Clock { now: Cell::new(0) };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_time_base(&clock);
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NSS, 0, DataType::ULONG(50)));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NSS, 0, DataType::FLOAT(1.0)));
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NSH(129));
+        assert_eq!(
+            canas.driver.sent[0].message.service_code,
+            ServiceCodeEnum::NSS.as_u8()
+        );
+        assert_eq!(canas.driver.sent[0].message.message_code, NSS_OK);
+        assert_eq!(
+            canas.driver.sent[1].message.message_code,
+            NSS_INVALID_TIMESTAMP
+        );
+        assert_eq!(canas.clock_offset(), Some(50));
+    }
+}
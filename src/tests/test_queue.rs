@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod rxqueue {
+    use crate::{
+        message::{CANAerospaceFrame, RawMessage},
+        queue::{Overflow, OverflowPolicy, RxQueue},
+        types::MessageType,
+    };
+
+    fn frame(id: u16) -> CANAerospaceFrame {
+        CANAerospaceFrame {
+            message_type: MessageType::from(id),
+            message: RawMessage::empty(),
+        }
+    }
+
+    fn drain<const N: usize>(queue: &mut RxQueue<N>) -> heapless::Vec<u16, N> {
+        let mut ids = heapless::Vec::new();
+        while let Some(f) = queue.pop() {
+            ids.push(f.message_type.id()).unwrap();
+        }
+        ids
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let mut queue: RxQueue<4> = RxQueue::new();
+        for id in [1800, 300, 128, 2000].iter() {
+            queue.push(frame(*id)).unwrap();
+        }
+        assert_eq!(queue.len(), 4);
+        assert_eq!(drain(&mut queue), [128, 300, 1800, 2000]);
+    }
+
+    #[test]
+    fn test_same_id_fifo() {
+        let mut queue: RxQueue<64> = RxQueue::new();
+        for code in 0..64u8 {
+            let mut f = frame(if code % 3 == 0 { 1800 } else { 300 });
+            f.message.message_code = code;
+            queue.push(f).unwrap();
+        }
+        let mut last: Option<(u16, u8)> = None;
+        while let Some(f) = queue.pop() {
+            let current = (f.message_type.id(), f.message.message_code);
+            if let Some(last) = last {
+                assert!(last < current);
+            }
+            last = Some(current);
+        }
+        assert_eq!(last, Some((1800, 63)));
+    }
+
+    #[test]
+    fn test_same_id_fifo_sequence_wrap() {
+        let mut queue: RxQueue<8> = RxQueue::new();
+        queue.sequence = u32::MAX - 3;
+        for code in 0..8u8 {
+            let mut f = frame(300);
+            f.message.message_code = code;
+            queue.push(f).unwrap();
+        }
+        for code in 0..8u8 {
+            assert_eq!(queue.pop().unwrap().message.message_code, code);
+        }
+    }
+
+    #[test]
+    fn test_drop_lowest_priority_same_id() {
+        let mut queue: RxQueue<2> = RxQueue::new();
+        queue.set_policy(OverflowPolicy::DropLowestPriority);
+        for code in 0..3u8 {
+            let mut f = frame(300);
+            f.message.message_code = code;
+            queue.push(f).unwrap_or(());
+        }
+        assert_eq!(queue.pop().unwrap().message.message_code, 0);
+        assert_eq!(queue.pop().unwrap().message.message_code, 1);
+    }
+
+    #[test]
+    fn test_drop_newest() {
+        let mut queue: RxQueue<2> = RxQueue::new();
+        queue.push(frame(300)).unwrap();
+        queue.push(frame(301)).unwrap();
+        assert!(matches!(
+            queue.push(frame(100)),
+            Err(Overflow::DroppedIncoming)
+        ));
+        assert_eq!(drain(&mut queue), [300, 301]);
+    }
+
+    #[test]
+    fn test_drop_lowest_priority() {
+        let mut queue: RxQueue<3> = RxQueue::new();
+        queue.set_policy(OverflowPolicy::DropLowestPriority);
+        queue.push(frame(300)).unwrap();
+        queue.push(frame(1800)).unwrap();
+        queue.push(frame(301)).unwrap();
+        assert!(matches!(
+            queue.push(frame(100)),
+            Err(Overflow::DroppedQueued)
+        ));
+        assert_eq!(drain(&mut queue), [100, 300, 301]);
+    }
+
+    #[test]
+    fn test_drop_lowest_priority_incoming() {
+        let mut queue: RxQueue<2> = RxQueue::new();
+        queue.set_policy(OverflowPolicy::DropLowestPriority);
+        queue.push(frame(300)).unwrap();
+        queue.push(frame(301)).unwrap();
+        assert!(matches!(
+            queue.push(frame(1800)),
+            Err(Overflow::DroppedIncoming)
+        ));
+        assert_eq!(drain(&mut queue), [300, 301]);
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut queue: RxQueue<1> = RxQueue::new();
+        queue.set_policy(OverflowPolicy::Reject);
+        queue.push(frame(300)).unwrap();
+        assert!(matches!(queue.push(frame(100)), Err(Overflow::Rejected)));
+        assert_eq!(drain(&mut queue), [300]);
+    }
+}
+
+#[cfg(test)]
+mod canaerospacelite {
+    use crate::{
+        error::Error,
+        message::{CANAerospaceFrame, RawMessage},
+        queue::OverflowPolicy,
+        tests::mock::MockDriver,
+        types::MessageType,
+        CANAerospaceLite, DEFAULT_RX_QUEUE_CAPACITY,
+    };
+
+    fn frame(id: u16) -> CANAerospaceFrame {
+        CANAerospaceFrame {
+            message_type: MessageType::from(id),
+            message: RawMessage::empty(),
+        }
+    }
+
+    #[test]
+    fn test_default_capacity() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for i in 0..DEFAULT_RX_QUEUE_CAPACITY as u16 + 1 {
+            canas.driver.queue(frame(300 + i));
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.rx_queue.len(), DEFAULT_RX_QUEUE_CAPACITY);
+        assert_eq!(canas.rx_overflow_count(), 1);
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut canas: CANAerospaceLite<_, 32> =
+            CANAerospaceLite::with_capacity(10, MockDriver::new());
+        for i in 0..32 {
+            canas.driver.queue(frame(300 + i));
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.rx_queue.len(), 32);
+        assert_eq!(canas.rx_overflow_count(), 0);
+    }
+
+    #[test]
+    fn test_read_message_same_id_fifo() {
+        let mut canas: CANAerospaceLite<_, 32> =
+            CANAerospaceLite::with_capacity(10, MockDriver::new());
+        for code in 0..32u8 {
+            let mut f = frame(300);
+            f.message.message_code = code;
+            canas.driver.queue(f);
+            canas.notify_receive_event().unwrap();
+        }
+        for code in 0..32u8 {
+            assert_eq!(canas.read_message().unwrap().message_code, code);
+        }
+    }
+
+    #[test]
+    fn test_overflow_reject() {
+        let mut canas: CANAerospaceLite<_, 1> =
+            CANAerospaceLite::with_capacity(10, MockDriver::new());
+        canas.set_overflow_policy(OverflowPolicy::Reject);
+        canas.driver.queue(frame(300));
+        canas.driver.queue(frame(301));
+        assert!(canas.notify_receive_event().is_ok());
+        assert_eq!(canas.notify_receive_event(), Err(Error::QueueFull));
+        assert_eq!(canas.rx_overflow_count(), 1);
+        assert_eq!(canas.read_message().unwrap().message_type.id(), 300);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod scheduler {
+    use crate::{
+        error::Error,
+        scheduler::SCHEDULE_TABLE_SIZE,
+        tests::mock::MockDriver,
+        types::{DataType, MessageType},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_schedule_invalid_identifier() {
+        let mut source = || DataType::NODATA;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.schedule(MessageType::NSH(128), 10, &mut source),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            canas.set_interval(MessageType::NOD(300), 10),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(canas.interval(MessageType::NOD(300)), None);
+    }
+
+    #[test]
+    fn test_schedule_table_full() {
+        let mut sources: [fn() -> DataType; SCHEDULE_TABLE_SIZE + 1] =
+            [|| DataType::NODATA; SCHEDULE_TABLE_SIZE + 1];
+        let (last, sources) = sources.split_last_mut().unwrap();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for (i, source) in sources.iter_mut().enumerate() {
+            canas
+                .schedule(MessageType::NOD(300 + i as u16), 10, source)
+                .unwrap();
+        }
+        assert_eq!(
+            canas.schedule(MessageType::UDL(1800), 10, last),
+            Err(Error::TableFull)
+        );
+    }
+
+    #[test]
+    fn test_poll() {
+        let mut counter = 0;
+        let mut fast = || {
+            counter += 1;
+            DataType::ULONG(counter)
+        };
+        let mut slow = || DataType::USHORT(7);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 10, &mut fast)
+            .unwrap();
+        canas
+            .schedule(MessageType::UDH(200), 25, &mut slow)
+            .unwrap();
+        assert_eq!(canas.interval(MessageType::NOD(315)), Some(10));
+
+        canas.poll(u32::MAX - 4).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(canas.driver.sent[0].message.node_id, 10);
+
+        canas.poll(4).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+
+        // interval elapsed across the wrap around of the timestamp
+        canas.poll(5).unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+        assert_eq!(canas.driver.sent[2].message_type, MessageType::NOD(315));
+        assert_eq!(canas.driver.sent[2].message.message_code, 1);
+        assert_eq!(
+            canas.driver.sent[2].message.payload.data,
+            DataType::ULONG(2).to_be_bytes()
+        );
+
+        canas.poll(20).unwrap();
+        assert_eq!(canas.driver.sent.len(), 5);
+        assert_eq!(canas.driver.sent[4].message_type, MessageType::UDH(200));
+    }
+
+    #[test]
+    fn test_disable_and_enable() {
+        let mut source = || DataType::NODATA;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 10, &mut source)
+            .unwrap();
+        canas.poll(0).unwrap();
+        canas.set_interval(MessageType::NOD(315), 0).unwrap();
+        canas.poll(100).unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+
+        // enabled again it is sent immediately
+        canas.set_interval(MessageType::NOD(315), 50).unwrap();
+        canas.poll(101).unwrap();
+        canas.poll(150).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        canas.poll(151).unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+
+        assert!(canas.unschedule(MessageType::NOD(315)).is_some());
+        canas.poll(1000).unwrap();
+        assert_eq!(canas.driver.sent.len(), 3);
+    }
+
+    #[test]
+    fn test_poll_driver_error() {
+        let mut source = || DataType::NODATA;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 10, &mut source)
+            .unwrap();
+        canas.driver.fail_send = true;
+        assert!(canas.poll(0).is_err());
+        canas.driver.fail_send = false;
+        canas.poll(1).unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+    }
+}
@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod simulation {
+    use crate::{
+        error::Error,
+        message::CANAerospaceMessage,
+        service::{
+            scs::{
+                SCS_INJECT, SCS_INVALID, SCS_QUERY, SCS_SIMULATION_OFF, SCS_SIMULATION_ON,
+                SIMULATION_TABLE_SIZE,
+            },
+            SERVICE_NOT_SUPPORTED,
+        },
+        tests::mock::{request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    fn output(data: DataType) -> CANAerospaceMessage {
+        CANAerospaceMessage::new(MessageType::NOD(315), 0, 0, 0, data)
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_SIMULATION_ON,
+            DataType::USHORT(315),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(
+            canas.driver.sent[0].message.message_code,
+            SERVICE_NOT_SUPPORTED
+        );
+        assert!(!canas.is_simulated(MessageType::NOD(315)));
+    }
+
+    #[test]
+    fn test_api() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.set_simulation(MessageType::NSH(128), true),
+            Err(Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            canas.inject_simulated_value(MessageType::NOD(315), DataType::FLOAT(1.0)),
+            Err(Error::InvalidIdentifier)
+        );
+
+        canas.set_simulation(MessageType::NOD(315), true).unwrap();
+        assert!(canas.is_simulated(MessageType::NOD(315)));
+        assert_eq!(canas.simulated_value(MessageType::NOD(315)), None);
+
+        // nothing is transmitted until a value is injected
+        canas.send_message(output(DataType::FLOAT(2.0))).unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+
+        canas
+            .inject_simulated_value(MessageType::NOD(315), DataType::FLOAT(1.0))
+            .unwrap();
+        canas.send_message(output(DataType::FLOAT(2.0))).unwrap();
+        assert_eq!(
+            canas.driver.sent[0].message.payload.data,
+            DataType::FLOAT(1.0).to_be_bytes()
+        );
+
+        canas.set_simulation(MessageType::NOD(315), false).unwrap();
+        assert!(!canas.is_simulated(MessageType::NOD(315)));
+        canas.send_message(output(DataType::FLOAT(2.0))).unwrap();
+        assert_eq!(
+            canas.driver.sent[1].message.payload.data,
+            DataType::FLOAT(2.0).to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn test_table_full() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for i in 0..SIMULATION_TABLE_SIZE as u16 {
+            canas
+                .set_simulation(MessageType::NOD(300 + i), true)
+                .unwrap();
+        }
+        canas.set_simulation(MessageType::NOD(300), true).unwrap();
+        assert_eq!(
+            canas.set_simulation(MessageType::UDL(1800), true),
+            Err(Error::TableFull)
+        );
+    }
+
+    #[test]
+    fn test_remote() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_simulation_control(true);
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_INJECT,
+            DataType::FLOAT(1.0),
+        ));
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_SIMULATION_ON,
+            DataType::USHORT(315),
+        ));
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_INJECT,
+            DataType::ULONG(3),
+        ));
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_QUERY,
+            DataType::USHORT(316),
+        ));
+        for _ in 0..4 {
+            canas.notify_receive_event().unwrap();
+        }
+        let codes: heapless::Vec<u8, 4> = canas
+            .driver
+            .sent
+            .iter()
+            .map(|frame| frame.message.message_code)
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                SCS_INVALID,
+                SCS_SIMULATION_ON,
+                SCS_SIMULATION_ON,
+                SCS_SIMULATION_OFF
+            ]
+        );
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NSH(129));
+        assert_eq!(
+            canas.driver.sent[1].message.payload.data,
+            DataType::USHORT(315).to_be_bytes()
+        );
+        assert_eq!(
+            canas.simulated_value(MessageType::NOD(315)),
+            Some(DataType::ULONG(3))
+        );
+
+        // broadcasts are executed without response
+        canas.driver.queue(request(
+            0,
+            ServiceCodeEnum::SCS,
+            SCS_SIMULATION_OFF,
+            DataType::USHORT(315),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 4);
+        assert!(!canas.is_simulated(MessageType::NOD(315)));
+    }
+
+    #[test]
+    fn test_remote_invalid() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_simulation_control(true);
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_SIMULATION_ON,
+            DataType::USHORT(128),
+        ));
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_QUERY,
+            DataType::USHORT(2000),
+        ));
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::SCS,
+            SCS_SIMULATION_ON,
+            DataType::ULONG(315),
+        ));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::SCS, 7, DataType::USHORT(315)));
+        for _ in 0..4 {
+            canas.notify_receive_event().unwrap();
+        }
+        assert!(canas
+            .driver
+            .sent
+            .iter()
+            .all(|frame| frame.message.message_code == SCS_INVALID));
+    }
+
+    #[test]
+    fn test_disable_clears_simulation() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_simulation_control(true);
+        canas.set_simulation(MessageType::UDH(200), true).unwrap();
+        canas.set_simulation_control(false);
+        assert!(!canas.is_simulated(MessageType::UDH(200)));
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod serviceregistry {
+    use crate::{
+        error::Error,
+        message::{CANAerospaceFrame, CANAerospaceMessage, Payload, RawMessage},
+        service::{ServiceHandler, ServiceResponse, SERVICE_HANDLER_TABLE_SIZE},
+        tests::mock::MockDriver,
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    fn request(message_type: MessageType, node_id: u8, service_code: u8) -> CANAerospaceFrame {
+        CANAerospaceFrame {
+            message_type,
+            message: RawMessage {
+                node_id,
+                data_type: DataType::ULONG(0).type_id(),
+                service_code,
+                message_code: 0x11,
+                payload: Payload::from(0xCAFEu32.to_be_bytes()),
+            },
+        }
+    }
+
+    struct Counter {
+        calls: u8,
+    }
+
+    impl ServiceHandler for Counter {
+        fn handle(&mut self, request: &CANAerospaceMessage) -> Option<ServiceResponse> {
+            self.calls += 1;
+            Some(ServiceResponse {
+                message_code: request.message_code,
+                data: DataType::UCHAR(self.calls),
+            })
+        }
+    }
+
+    #[test]
+    fn test_closure_handler() {
+        let mut echo = |request: &CANAerospaceMessage| {
+            Some(ServiceResponse {
+                message_code: 0,
+                data: request.data,
+            })
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .register_service_handler(ServiceCodeEnum::CUSTOM(100), &mut echo)
+            .unwrap();
+        canas.driver.queue(request(MessageType::NSL(2000), 10, 100));
+        canas.notify_receive_event().unwrap();
+
+        assert_eq!(canas.driver.sent.len(), 1);
+        let frame = &canas.driver.sent[0];
+        assert_eq!(frame.message_type, MessageType::NSL(2001));
+        assert_eq!(frame.message.node_id, 10);
+        assert_eq!(frame.message.service_code, 100);
+        assert_eq!(frame.message.message_code, 0);
+        assert_eq!(frame.message.payload.data, 0xCAFEu32.to_be_bytes());
+        assert_eq!(canas.statistics().service_requests_handled, 1);
+        assert!(canas.read_message().is_none());
+    }
+
+    #[test]
+    fn test_struct_handler_broadcast() {
+        let mut counter = Counter { calls: 0 };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .register_service_handler(ServiceCodeEnum::NSS, &mut counter)
+            .unwrap();
+        canas.driver.queue(request(MessageType::NSH(130), 0, 1));
+        canas.driver.queue(request(MessageType::NSH(130), 10, 1));
+        canas.driver.queue(request(MessageType::NSH(130), 11, 1));
+        for _ in 0..3 {
+            canas.notify_receive_event().unwrap();
+        }
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(canas.driver.sent[1].message_type, MessageType::NSH(131));
+        assert_eq!(canas.driver.sent[1].message.message_code, 0x11);
+        assert_eq!(canas.driver.sent[1].message.payload.data[0], 2);
+        drop(canas);
+        assert_eq!(counter.calls, 2);
+    }
+
+    #[test]
+    fn test_handler_without_response() {
+        let mut silent = |_: &CANAerospaceMessage| None;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .register_service_handler(ServiceCodeEnum::TIS, &mut silent)
+            .unwrap();
+        canas.driver.queue(request(MessageType::NSH(128), 10, 5));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+        assert_eq!(canas.statistics().service_requests_handled, 1);
+    }
+
+    #[test]
+    fn test_override_ids() {
+        let mut ids = |_: &CANAerospaceMessage| {
+            Some(ServiceResponse {
+                message_code: 0,
+                data: DataType::UCHAR4(1, 2, 3, 4),
+            })
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .register_service_handler(ServiceCodeEnum::IDS, &mut ids)
+            .unwrap();
+        canas.driver.queue(request(MessageType::NSH(128), 10, 0));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent[0].message.payload.data, [1, 2, 3, 4]);
+        assert_eq!(canas.statistics().ids_requests_answered, 0);
+    }
+
+    #[test]
+    fn test_unsupported_broadcast_not_answered() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.queue(request(MessageType::NSH(128), 0, 100));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+        assert!(canas.read_message().is_none());
+    }
+
+    #[test]
+    fn test_replace_and_unregister() {
+        let mut first = |_: &CANAerospaceMessage| {
+            Some(ServiceResponse {
+                message_code: 1,
+                data: DataType::NODATA,
+            })
+        };
+        let mut second = |_: &CANAerospaceMessage| {
+            Some(ServiceResponse {
+                message_code: 2,
+                data: DataType::NODATA,
+            })
+        };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .register_service_handler(ServiceCodeEnum::CUSTOM(200), &mut first)
+            .unwrap();
+        canas
+            .register_service_handler(ServiceCodeEnum::CUSTOM(200), &mut second)
+            .unwrap();
+        canas.driver.queue(request(MessageType::NSH(128), 10, 200));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent[0].message.message_code, 2);
+
+        assert!(canas
+            .unregister_service_handler(ServiceCodeEnum::CUSTOM(200))
+            .is_some());
+        assert!(canas
+            .unregister_service_handler(ServiceCodeEnum::CUSTOM(200))
+            .is_none());
+    }
+
+    #[test]
+    fn test_register_invalid_and_full() {
+        let mut handlers = [
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+            Counter { calls: 0 },
+        ];
+        assert_eq!(handlers.len(), SERVICE_HANDLER_TABLE_SIZE + 1);
+        let mut unknown = Counter { calls: 0 };
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.register_service_handler(ServiceCodeEnum::UNKNOWN, &mut unknown),
+            Err(Error::InvalidServiceCode)
+        );
+        let (last, rest) = handlers.split_last_mut().unwrap();
+        for (i, handler) in rest.iter_mut().enumerate() {
+            canas
+                .register_service_handler(ServiceCodeEnum::CUSTOM(100 + i as u8), handler)
+                .unwrap();
+        }
+        assert_eq!(
+            canas.register_service_handler(ServiceCodeEnum::CUSTOM(150), last),
+            Err(Error::TableFull)
+        );
+    }
+}
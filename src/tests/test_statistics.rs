@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod nodestatistics {
+    use crate::{
+        message::{CANAerospaceFrame, CANAerospaceMessage, RawMessage},
+        statistics::{NodeStatistics, StatisticsCounter},
+        tests::mock::{request, MockDriver},
+        types::{ChannelPriority, DataType, MessageType, ServiceChannel, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_get() {
+        let statistics = NodeStatistics {
+            frames_received: 1,
+            frames_sent: 2,
+            ids_requests_answered: 3,
+            service_requests_handled: 4,
+            service_requests_queued: 9,
+            invalid_frames_discarded: 5,
+            frames_filtered: 8,
+            queue_overflows: 6,
+            driver_errors: 7,
+        };
+        assert_eq!(statistics.get(StatisticsCounter::FramesReceived), 1);
+        assert_eq!(statistics.get(StatisticsCounter::FramesSent), 2);
+        assert_eq!(statistics.get(StatisticsCounter::IdsRequestsAnswered), 3);
+        assert_eq!(statistics.get(StatisticsCounter::ServiceRequestsHandled), 4);
+        assert_eq!(statistics.get(StatisticsCounter::InvalidFramesDiscarded), 5);
+        assert_eq!(statistics.get(StatisticsCounter::QueueOverflows), 6);
+        assert_eq!(statistics.get(StatisticsCounter::DriverErrors), 7);
+        assert_eq!(statistics.get(StatisticsCounter::FramesFiltered), 8);
+        assert_eq!(statistics.get(StatisticsCounter::ServiceRequestsQueued), 9);
+    }
+
+    #[test]
+    fn test_counters() {
+        let mut handler = |_: &CANAerospaceMessage| None;
+        let mut canas: CANAerospaceLite<_, 2> =
+            CANAerospaceLite::with_capacity(10, MockDriver::new());
+        canas
+            .register_service_handler(ServiceCodeEnum::NSS, &mut handler)
+            .unwrap();
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::IDS, 0, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::NSS, 0, DataType::NODATA));
+        canas.driver.queue(CANAerospaceFrame {
+            message_type: MessageType::INVALID,
+            message: RawMessage::empty(),
+        });
+        canas.driver.queue(CANAerospaceFrame {
+            message_type: MessageType::NOD(300),
+            message: RawMessage::empty(),
+        });
+        canas.driver.queue(CANAerospaceFrame {
+            message_type: MessageType::NOD(301),
+            message: RawMessage::empty(),
+        });
+        canas.driver.queue(CANAerospaceFrame {
+            message_type: MessageType::NOD(302),
+            message: RawMessage::empty(),
+        });
+        for _ in 0..7 {
+            canas.notify_receive_event().unwrap();
+        }
+        canas.driver.fail_send = true;
+        assert!(canas
+            .send_message(CANAerospaceMessage::new(
+                MessageType::NOD(300),
+                10,
+                0,
+                0,
+                DataType::NODATA,
+            ))
+            .is_err());
+
+        let statistics = canas.statistics();
+        assert_eq!(statistics.frames_received, 6);
+        assert_eq!(statistics.frames_sent, 1);
+        assert_eq!(statistics.ids_requests_answered, 1);
+        assert_eq!(statistics.service_requests_handled, 1);
+        assert_eq!(statistics.invalid_frames_discarded, 1);
+        assert_eq!(statistics.queue_overflows, 1);
+        assert_eq!(statistics.driver_errors, 1);
+        assert_eq!(canas.rx_overflow_count(), 1);
+    }
+
+    #[test]
+    fn test_queued_service_requests() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.set_service_channels(&[ServiceChannel::new(ChannelPriority::High, 1).unwrap()]);
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::IDS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        assert!(canas.driver.sent.is_empty());
+        assert_eq!(canas.statistics().service_requests_queued, 1);
+        assert_eq!(canas.statistics().service_requests_handled, 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.fail_recv = true;
+        assert!(canas.notify_receive_event().is_err());
+        assert_eq!(canas.statistics().driver_errors, 1);
+        canas.reset_statistics();
+        assert_eq!(canas.statistics(), NodeStatistics::default());
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "statistics-publish")]
+mod publish {
+    use crate::{
+        error::Error,
+        statistics::{StatisticsCounter, STATISTICS_PUBLISH_SIZE},
+        tests::mock::MockDriver,
+        types::{DataType, MessageType},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_publish_invalid_identifier() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.publish_statistics_counter(StatisticsCounter::FramesSent, MessageType::NSH(128)),
+            Err(Error::InvalidIdentifier)
+        );
+    }
+
+    #[test]
+    fn test_publish_table_full() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for i in 0..STATISTICS_PUBLISH_SIZE as u16 {
+            canas
+                .publish_statistics_counter(
+                    StatisticsCounter::FramesSent,
+                    MessageType::NOD(300 + i),
+                )
+                .unwrap();
+        }
+        assert_eq!(
+            canas.publish_statistics_counter(StatisticsCounter::FramesSent, MessageType::UDL(1800)),
+            Err(Error::TableFull)
+        );
+    }
+
+    #[test]
+    fn test_poll_statistics() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .publish_statistics_counter(StatisticsCounter::FramesSent, MessageType::UDL(1850))
+            .unwrap();
+        canas
+            .publish_statistics_counter(StatisticsCounter::DriverErrors, MessageType::NOD(1700))
+            .unwrap();
+
+        canas.poll_statistics(0).unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+
+        canas.set_statistics_interval(100);
+        canas.poll_statistics(u32::MAX - 10).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(canas.driver.sent[0].message_type.id(), 1850);
+        assert_eq!(canas.driver.sent[0].message.node_id, 10);
+        assert_eq!(
+            canas.driver.sent[0].message.payload.data,
+            DataType::ULONG(0).to_be_bytes()
+        );
+        assert_eq!(canas.driver.sent[1].message_type.id(), 1700);
+
+        canas.poll_statistics(50).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+
+        // interval elapsed across the wrap around of the timestamp
+        canas.poll_statistics(89).unwrap();
+        assert_eq!(canas.driver.sent.len(), 4);
+        assert_eq!(
+            canas.driver.sent[2].message.payload.data,
+            DataType::ULONG(2).to_be_bytes()
+        );
+    }
+}
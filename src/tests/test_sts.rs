@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod statetransmission {
+    use crate::{
+        error::Error,
+        message::CANAerospaceMessage,
+        service::sts::STATE_TABLE_SIZE,
+        tests::mock::{request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    fn output(message_type: MessageType, data: DataType) -> CANAerospaceMessage {
+        CANAerospaceMessage::new(message_type, 0, 0, 0, data)
+    }
+
+    /// Polls the state transmission until it is done and returns the number of polls
+    fn poll_all(canas: &mut CANAerospaceLite<MockDriver>) -> usize {
+        let mut polls = 0;
+        while canas.sts_transmitting() {
+            let sent = canas.driver.sent.len();
+            canas.poll_sts().unwrap();
+            assert!(canas.driver.sent.len() <= sent + 1);
+            polls += 1;
+        }
+        polls
+    }
+
+    #[test]
+    fn test_register_state_value() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        assert_eq!(
+            canas.register_state_value(MessageType::NSL(2000)),
+            Err(Error::InvalidIdentifier)
+        );
+        for i in 0..STATE_TABLE_SIZE as u16 {
+            canas
+                .register_state_value(MessageType::NOD(300 + i))
+                .unwrap();
+        }
+        canas.register_state_value(MessageType::NOD(300)).unwrap();
+        assert_eq!(
+            canas.register_state_value(MessageType::UDH(200)),
+            Err(Error::TableFull)
+        );
+    }
+
+    #[test]
+    fn test_transmit_state() {
+        let mut altitude = || DataType::ULONG(1200);
+        let mut disabled = || DataType::ULONG(0);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 1000, &mut altitude)
+            .unwrap();
+        canas
+            .schedule(MessageType::NOD(316), 0, &mut disabled)
+            .unwrap();
+        canas.register_state_value(MessageType::NOD(315)).unwrap();
+        canas.register_state_value(MessageType::UDL(1800)).unwrap();
+        canas.register_state_value(MessageType::UDH(200)).unwrap();
+        canas
+            .send_message(output(MessageType::UDL(1800), DataType::USHORT(7)))
+            .unwrap();
+        canas
+            .send_message(output(MessageType::NOD(300), DataType::USHORT(8)))
+            .unwrap();
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::STS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert_eq!(poll_all(&mut canas), 3);
+
+        let sent = &canas.driver.sent;
+        assert_eq!(sent.len(), 5);
+        assert_eq!(sent[2].message_type, MessageType::NOD(315));
+        assert_eq!(
+            sent[2].message.payload.data,
+            DataType::ULONG(1200).to_be_bytes()
+        );
+        assert_eq!(sent[3].message_type, MessageType::UDL(1800));
+        assert_eq!(
+            sent[3].message.payload.data,
+            DataType::USHORT(7).to_be_bytes()
+        );
+        assert_eq!(sent[4].message_type, MessageType::NSH(129));
+        assert_eq!(sent[4].message.message_code, 2);
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let mut source = || DataType::ULONG(1);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 1000, &mut source)
+            .unwrap();
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::STS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(poll_all(&mut canas), 1);
+        assert_eq!(canas.driver.sent.len(), 1);
+        assert_eq!(canas.driver.sent[0].message_type, MessageType::NOD(315));
+    }
+
+    #[test]
+    fn test_simulated_without_value_not_counted() {
+        let mut pressure = || DataType::FLOAT(1013.25);
+        let mut altitude = || DataType::ULONG(1200);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 1000, &mut pressure)
+            .unwrap();
+        canas
+            .schedule(MessageType::NOD(316), 1000, &mut altitude)
+            .unwrap();
+        canas.set_simulation_control(true);
+        canas.set_simulation(MessageType::NOD(315), true).unwrap();
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::STS, 0, DataType::NODATA));
+        canas.notify_receive_event().unwrap();
+        poll_all(&mut canas);
+        let sent = &canas.driver.sent;
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].message_type, MessageType::NOD(316));
+        assert_eq!(sent[1].message_type, MessageType::NSH(129));
+        assert_eq!(sent[1].message.message_code, 1);
+    }
+
+    #[test]
+    fn test_driver_error() {
+        let mut source = || DataType::ULONG(1);
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 1000, &mut source)
+            .unwrap();
+        canas.transmit_state();
+        canas.driver.fail_send = true;
+        assert!(canas.poll_sts().is_err());
+        assert!(canas.sts_transmitting());
+        canas.driver.fail_send = false;
+        assert_eq!(poll_all(&mut canas), 1);
+        assert_eq!(canas.driver.sent.len(), 1);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod testcontrol {
+    use crate::{
+        error::Error,
+        service::tcs::{TCS_FAILED, TCS_PASSED, TCS_UNKNOWN_TEST, TEST_TABLE_SIZE},
+        tests::mock::{request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_register_table_full() {
+        let mut tests: [fn() -> u32; TEST_TABLE_SIZE + 1] = [|| 0; TEST_TABLE_SIZE + 1];
+        let (last, tests) = tests.split_last_mut().unwrap();
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        for (id, test) in tests.iter_mut().enumerate() {
+            canas.register_built_in_test(id as u8, test).unwrap();
+        }
+        assert_eq!(
+            canas.register_built_in_test(0xFF, last),
+            Err(Error::TableFull)
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let mut runs = 0;
+        let mut passing = || {
+            runs += 1;
+            0
+        };
+        let mut failing = || 0x0BAD;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.register_built_in_test(1, &mut passing).unwrap();
+        canas.register_built_in_test(2, &mut failing).unwrap();
+        assert_eq!(canas.built_in_test_result(1), None);
+        assert_eq!(canas.run_built_in_test(1), Some(0));
+        assert_eq!(canas.run_built_in_test(3), None);
+        assert_eq!(canas.built_in_test_result(1), Some(0));
+
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::TCS, 1, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::TCS, 2, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::TCS, 3, DataType::NODATA));
+        canas
+            .driver
+            .queue(request(0, ServiceCodeEnum::TCS, 1, DataType::NODATA));
+        for _ in 0..4 {
+            canas.notify_receive_event().unwrap();
+        }
+        let sent = &canas.driver.sent;
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0].message_type, MessageType::NSH(129));
+        assert_eq!(sent[0].message.message_code, TCS_PASSED);
+        assert_eq!(sent[1].message.message_code, TCS_FAILED);
+        assert_eq!(sent[1].message.data_type, DataType::BLONG(0).type_id());
+        assert_eq!(sent[1].message.payload.data, 0x0BADu32.to_be_bytes());
+        assert_eq!(sent[2].message.message_code, TCS_UNKNOWN_TEST);
+        assert_eq!(canas.built_in_test_result(2), Some(0x0BAD));
+        drop(canas);
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn test_publish() {
+        let mut passing = || 0;
+        let mut failing = || 1;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.register_built_in_test(0, &mut passing).unwrap();
+        canas.register_built_in_test(5, &mut failing).unwrap();
+        assert_eq!(
+            canas.publish_built_in_tests(MessageType::UDL(1800), 100),
+            Err(Error::InvalidIdentifier)
+        );
+        canas.poll_built_in_tests(0).unwrap();
+        assert_eq!(canas.driver.sent.len(), 0);
+
+        canas
+            .publish_built_in_tests(MessageType::NOD(1700), 100)
+            .unwrap();
+        canas.poll_built_in_tests(u32::MAX - 50).unwrap();
+        canas.poll_built_in_tests(48).unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+        let status = &canas.driver.sent[0];
+        assert_eq!(status.message_type, MessageType::NOD(1700));
+        assert_eq!(status.message.data_type, DataType::BLONG(0).type_id());
+        assert_eq!(status.message.payload.data, (1u32 << 5).to_be_bytes());
+
+        canas.poll_built_in_tests(49).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+
+        canas
+            .publish_built_in_tests(MessageType::NOD(1700), 0)
+            .unwrap();
+        canas.poll_built_in_tests(1000).unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod transmissioninterval {
+    use crate::{
+        service::tis::{TIS_INVALID, TIS_OK},
+        tests::mock::{request, MockDriver},
+        types::{DataType, MessageType, ServiceCodeEnum},
+        CANAerospaceLite,
+    };
+
+    #[test]
+    fn test_change_interval() {
+        let mut source = || DataType::NODATA;
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas
+            .schedule(MessageType::NOD(315), 10, &mut source)
+            .unwrap();
+
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::TIS,
+            0,
+            DataType::USHORT2(315, 250),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.interval(MessageType::NOD(315)), Some(250));
+        let response = &canas.driver.sent[0];
+        assert_eq!(response.message_type, MessageType::NSH(129));
+        assert_eq!(response.message.service_code, ServiceCodeEnum::TIS.as_u8());
+        assert_eq!(response.message.message_code, TIS_OK);
+        assert_eq!(response.message.data_type, DataType::NODATA.type_id());
+
+        // broadcasts are executed without response
+        canas.driver.queue(request(
+            0,
+            ServiceCodeEnum::TIS,
+            0,
+            DataType::USHORT2(315, 0),
+        ));
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.interval(MessageType::NOD(315)), Some(0));
+        assert_eq!(canas.driver.sent.len(), 1);
+        canas.poll(0).unwrap();
+        assert_eq!(canas.driver.sent.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut canas = CANAerospaceLite::new(10, MockDriver::new());
+        canas.driver.queue(request(
+            10,
+            ServiceCodeEnum::TIS,
+            0,
+            DataType::USHORT2(315, 250),
+        ));
+        canas
+            .driver
+            .queue(request(10, ServiceCodeEnum::TIS, 0, DataType::ULONG(315)));
+        canas.notify_receive_event().unwrap();
+        canas.notify_receive_event().unwrap();
+        assert_eq!(canas.driver.sent.len(), 2);
+        assert!(canas
+            .driver
+            .sent
+            .iter()
+            .all(|frame| frame.message.message_code == TIS_INVALID));
+    }
+}
@@ -48,31 +48,31 @@ mod messagetype {
     #[test]
     fn test_id() {
         for i in 0..=127 {
-            let m = MessageType::EED(i as u16);
+            let m = MessageType::EED(i);
             assert_eq!(m.id(), i);
         }
         for i in 128..=199 {
-            let m = MessageType::NSH(i as u16);
+            let m = MessageType::NSH(i);
             assert_eq!(m.id(), i);
         }
         for i in 200..=299 {
-            let m = MessageType::UDH(i as u16);
+            let m = MessageType::UDH(i);
             assert_eq!(m.id(), i);
         }
         for i in 300..=1799 {
-            let m = MessageType::NOD(i as u16);
+            let m = MessageType::NOD(i);
             assert_eq!(m.id(), i);
         }
         for i in 1800..=1899 {
-            let m = MessageType::UDL(i as u16);
+            let m = MessageType::UDL(i);
             assert_eq!(m.id(), i);
         }
         for i in 1900..=1999 {
-            let m = MessageType::DSD(i as u16);
+            let m = MessageType::DSD(i);
             assert_eq!(m.id(), i);
         }
         for i in 2000..=2031 {
-            let m = MessageType::NSL(i as u16);
+            let m = MessageType::NSL(i);
             assert_eq!(m.id(), i);
         }
     }
@@ -147,32 +147,32 @@ mod messagetype {
     #[test]
     fn test_from_u16() {
         for i in 0..=127 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::EED(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::EED(i));
         }
         for i in 128..=199 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::NSH(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::NSH(i));
         }
         for i in 200..=299 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::UDH(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::UDH(i));
         }
         for i in 300..=1799 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::NOD(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::NOD(i));
         }
         for i in 1800..=1899 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::UDL(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::UDL(i));
         }
         for i in 1900..=1999 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::DSD(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::DSD(i));
         }
         for i in 2000..=2031 {
-            let m = MessageType::from(i as u16);
-            assert_eq!(m, MessageType::NSL(i as u16));
+            let m = MessageType::from(i);
+            assert_eq!(m, MessageType::NSL(i));
         }
     }
 
@@ -297,13 +297,13 @@ mod datatypes {
     #[test]
     fn test_is_empty_true() {
         let dt_nodata = DataType::NODATA;
-        assert_eq!(dt_nodata.is_empty(), true);
+        assert!(dt_nodata.is_empty());
     }
 
     #[test]
     fn test_is_empty_false() {
         let dt_err = DataType::ERROR(0);
-        assert_eq!(dt_err.is_empty(), false);
+        assert!(!dt_err.is_empty());
     }
 
     #[test]
@@ -515,4 +515,59 @@ mod datatypes {
         let dt_resvd = DataType::RESVD(0xBCDE);
         assert_eq!(dt_resvd.to_be_bytes(), [0, 0, 188, 222]);
     }
+
+    #[test]
+    fn test_from_ushort2() {
+        let dt_us2 = DataType::USHORT2(0xDEAD, 0xBEEF);
+        let bytes = dt_us2.to_be_bytes();
+        assert_eq!(DataType::from((dt_us2.type_id(), &bytes[..])), dt_us2);
+    }
+}
+
+#[cfg(test)]
+mod servicechannel {
+    use crate::types::{ChannelPriority, MessageType, ServiceChannel};
+
+    #[test]
+    fn test_new() {
+        let high = ServiceChannel::new(ChannelPriority::High, 35).unwrap();
+        assert_eq!(high.priority(), ChannelPriority::High);
+        assert_eq!(high.number(), 35);
+        assert_eq!(high.request_id(), MessageType::NSH(198));
+        assert_eq!(high.response_id(), MessageType::NSH(199));
+        assert_eq!(ServiceChannel::new(ChannelPriority::High, 36), None);
+
+        let low = ServiceChannel::new(ChannelPriority::Low, 15).unwrap();
+        assert_eq!(low.request_id(), MessageType::NSL(2030));
+        assert_eq!(low.response_id(), MessageType::NSL(2031));
+        assert_eq!(ServiceChannel::new(ChannelPriority::Low, 16), None);
+    }
+
+    #[test]
+    fn test_from_id() {
+        let channel = ServiceChannel::new(ChannelPriority::High, 0).unwrap();
+        assert_eq!(
+            ServiceChannel::from_request_id(MessageType::NSH(128)),
+            Some(channel)
+        );
+        assert_eq!(
+            ServiceChannel::from_response_id(MessageType::NSH(129)),
+            Some(channel)
+        );
+        assert_eq!(ServiceChannel::from_request_id(MessageType::NSH(129)), None);
+        assert_eq!(
+            ServiceChannel::from_response_id(MessageType::NSH(128)),
+            None
+        );
+        assert_eq!(
+            ServiceChannel::from_response_id(MessageType::NSL(2031)),
+            ServiceChannel::new(ChannelPriority::Low, 15)
+        );
+        assert_eq!(
+            ServiceChannel::from_request_id(MessageType::NSL(2032)),
+            None
+        );
+        assert_eq!(ServiceChannel::from_request_id(MessageType::NSH(200)), None);
+        assert_eq!(ServiceChannel::from_request_id(MessageType::NOD(300)), None);
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! All required types to implement CANAerospace protocol is defined in this module.
 
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 
 use crate::message::RawMessage;
 
@@ -10,17 +10,19 @@ pub type MessageCode = u8;
 pub type ServiceCode = u8;
 pub type NodeId = u8;
 pub type IDSHeaderConfiguration = u8;
+/// Point in time in an application defined unit (e.g. milliseconds), expected to wrap around on overflow
+pub type Timestamp = u32;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct IDSConfiguration(pub u8);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct HardwareRevision(pub u8);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SoftwareRevision(pub u8);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct IDSResponse {
     pub hw_rev: HardwareRevision,
     pub sw_rev: SoftwareRevision,
@@ -28,6 +30,39 @@ pub struct IDSResponse {
     pub header: IDSHeaderConfiguration,
 }
 
+impl IDSResponse {
+    /// Reads the identification from the data of a [ServiceCodeEnum::IDS] response, None if it is not [DataType::UCHAR4]
+    ///```
+    /// # use can_aerospace_lite::types::{DataType, IDSResponse};
+    /// let identification = IDSResponse::from_data(DataType::UCHAR4(2, 1, 0, 0)).unwrap();
+    /// assert_eq!(identification.hw_rev.0, 2);
+    /// assert_eq!(DataType::from(identification), DataType::UCHAR4(2, 1, 0, 0));
+    ///```
+    pub fn from_data(data: DataType) -> Option<Self> {
+        match data {
+            DataType::UCHAR4(hw_rev, sw_rev, configuration, header) => Some(IDSResponse {
+                hw_rev: HardwareRevision(hw_rev),
+                sw_rev: SoftwareRevision(sw_rev),
+                configuration: IDSConfiguration(configuration),
+                header,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<IDSResponse> for DataType {
+    /// Data of a [ServiceCodeEnum::IDS] response
+    fn from(identification: IDSResponse) -> Self {
+        DataType::UCHAR4(
+            identification.hw_rev.0,
+            identification.sw_rev.0,
+            identification.configuration.0,
+            identification.header,
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ServiceCodeEnum {
     /// Identification Service (0x0)
@@ -142,6 +177,61 @@ impl From<u8> for ServiceCodeEnum {
     }
 }
 
+/// CAN bit rates which can be selected by [ServiceCodeEnum::BSS] requests
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bitrate {
+    /// 1 Mbit/s (code 0)
+    Kbps1000,
+    /// 500 kbit/s (code 1)
+    Kbps500,
+    /// 250 kbit/s (code 2)
+    Kbps250,
+    /// 125 kbit/s (code 3)
+    Kbps125,
+}
+
+impl Bitrate {
+    /// Returns the bit rate of a baud rate code, None if the code is not defined
+    ///```
+    /// # use can_aerospace_lite::types::Bitrate;
+    /// assert_eq!(Bitrate::from_code(2), Some(Bitrate::Kbps250));
+    /// assert_eq!(Bitrate::from_code(4), None);
+    ///```
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Bitrate::Kbps1000),
+            1 => Some(Bitrate::Kbps500),
+            2 => Some(Bitrate::Kbps250),
+            3 => Some(Bitrate::Kbps125),
+            _ => None,
+        }
+    }
+
+    /// Returns the baud rate code which is carried in the `message_code` of [ServiceCodeEnum::BSS] requests
+    pub fn code(&self) -> u8 {
+        match self {
+            Bitrate::Kbps1000 => 0,
+            Bitrate::Kbps500 => 1,
+            Bitrate::Kbps250 => 2,
+            Bitrate::Kbps125 => 3,
+        }
+    }
+
+    /// Returns the bit rate in bit/s
+    ///```
+    /// # use can_aerospace_lite::types::Bitrate;
+    /// assert_eq!(Bitrate::Kbps500.bits_per_second(), 500_000);
+    ///```
+    pub fn bits_per_second(&self) -> u32 {
+        match self {
+            Bitrate::Kbps1000 => 1_000_000,
+            Bitrate::Kbps500 => 500_000,
+            Bitrate::Kbps250 => 250_000,
+            Bitrate::Kbps125 => 125_000,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MessageType {
     /// Emergency Event Data \[0,127\]
@@ -216,6 +306,15 @@ impl MessageType {
             MessageType::INVALID => u16::MAX,
         }
     }
+
+    /// Returns the identifier of [MessageType::NOD], [MessageType::UDH] and [MessageType::UDL] messages
+    /// which carry the output data of a node
+    pub(crate) fn output_id(&self) -> Option<u16> {
+        match *self {
+            MessageType::NOD(id) | MessageType::UDH(id) | MessageType::UDL(id) => Some(id),
+            _ => None,
+        }
+    }
 }
 
 impl From<u16> for MessageType {
@@ -233,6 +332,106 @@ impl From<u16> for MessageType {
     }
 }
 
+/// Priority of a [ServiceChannel]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChannelPriority {
+    /// Channel on [MessageType::NSH] identifiers
+    High,
+    /// Channel on [MessageType::NSL] identifiers
+    Low,
+}
+
+/// Node service channel, a pair of an even request identifier and the following odd response identifier
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ServiceChannel {
+    priority: ChannelPriority,
+    number: u8,
+}
+
+impl ServiceChannel {
+    /// Number of high priority channels, on identifiers \[128, 199\]
+    pub const HIGH_PRIORITY_CHANNELS: u8 = 36;
+    /// Number of low priority channels, on identifiers \[2000, 2031\]
+    pub const LOW_PRIORITY_CHANNELS: u8 = 16;
+
+    /// Returns the channel with `number` counted from 0, None if the number is out of range
+    ///```
+    /// # use can_aerospace_lite::types::{ChannelPriority, MessageType, ServiceChannel};
+    /// let channel = ServiceChannel::new(ChannelPriority::High, 2).unwrap();
+    /// assert_eq!(channel.request_id(), MessageType::NSH(132));
+    /// assert_eq!(channel.response_id(), MessageType::NSH(133));
+    /// assert_eq!(ServiceChannel::new(ChannelPriority::Low, 16), None);
+    ///```
+    pub fn new(priority: ChannelPriority, number: u8) -> Option<Self> {
+        let channels = match priority {
+            ChannelPriority::High => Self::HIGH_PRIORITY_CHANNELS,
+            ChannelPriority::Low => Self::LOW_PRIORITY_CHANNELS,
+        };
+        (number < channels).then_some(ServiceChannel { priority, number })
+    }
+
+    /// Returns the channel whose request identifier is `message_type`, None for other identifiers
+    ///```
+    /// # use can_aerospace_lite::types::{ChannelPriority, MessageType, ServiceChannel};
+    /// let channel = ServiceChannel::from_request_id(MessageType::NSL(2004)).unwrap();
+    /// assert_eq!(channel, ServiceChannel::new(ChannelPriority::Low, 2).unwrap());
+    /// assert_eq!(ServiceChannel::from_request_id(MessageType::NSL(2005)), None);
+    ///```
+    pub fn from_request_id(message_type: MessageType) -> Option<Self> {
+        match Self::from_id(message_type)? {
+            (channel, 0) => Some(channel),
+            _ => None,
+        }
+    }
+
+    /// Returns the channel whose response identifier is `message_type`, None for other identifiers
+    pub fn from_response_id(message_type: MessageType) -> Option<Self> {
+        match Self::from_id(message_type)? {
+            (channel, 1) => Some(channel),
+            _ => None,
+        }
+    }
+
+    /// Returns the channel of a service identifier and 0 for request or 1 for response identifiers
+    fn from_id(message_type: MessageType) -> Option<(Self, u16)> {
+        let (priority, offset) = match message_type {
+            MessageType::NSH(id) => (ChannelPriority::High, id.checked_sub(128)?),
+            MessageType::NSL(id) => (ChannelPriority::Low, id.checked_sub(2000)?),
+            _ => return None,
+        };
+        let number = u8::try_from(offset / 2).ok()?;
+        Some((Self::new(priority, number)?, offset % 2))
+    }
+
+    /// Returns the priority of the channel
+    pub fn priority(&self) -> ChannelPriority {
+        self.priority
+    }
+
+    /// Returns the number of the channel, counted from 0 within its priority
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Returns the identifier of the requests on this channel
+    pub fn request_id(&self) -> MessageType {
+        self.id(0)
+    }
+
+    /// Returns the identifier of the responses on this channel
+    pub fn response_id(&self) -> MessageType {
+        self.id(1)
+    }
+
+    fn id(&self, response: u16) -> MessageType {
+        let offset = u16::from(self.number) * 2 + response;
+        match self.priority {
+            ChannelPriority::High => MessageType::NSH(128 + offset),
+            ChannelPriority::Low => MessageType::NSL(2000 + offset),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DataType {
     NODATA,
@@ -393,11 +592,11 @@ impl DataType {
                 [a_bytes[0], a_bytes[1], b_bytes[0], b_bytes[1]]
             }
             DataType::CHAR4(a, b, c, d) => [a as u8, b as u8, c as u8, d as u8],
-            DataType::UCHAR4(a, b, c, d) => [a as u8, b as u8, c as u8, d as u8],
-            DataType::BCHAR4(a, b, c, d) => [a as u8, b as u8, c as u8, d as u8],
+            DataType::UCHAR4(a, b, c, d) => [a, b, c, d],
+            DataType::BCHAR4(a, b, c, d) => [a, b, c, d],
             DataType::CHAR2(a, b) => [a as u8, b as u8, 0, 0],
-            DataType::UCHAR2(a, b) => [a as u8, b as u8, 0, 0],
-            DataType::BCHAR2(a, b) => [a as u8, b as u8, 0, 0],
+            DataType::UCHAR2(a, b) => [a, b, 0, 0],
+            DataType::BCHAR2(a, b) => [a, b, 0, 0],
             DataType::MEMID(d) => d.to_be_bytes(),
             DataType::CHKSUM(d) => d.to_be_bytes(),
             DataType::ACHAR(a) => [a, 0, 0, 0],
@@ -437,10 +636,10 @@ impl From<(u8, &[u8])> for DataType {
             0x7 => DataType::USHORT(as_u16(arr)),
             0x8 => DataType::BSHORT(as_u16(arr)),
             0x9 => DataType::CHAR(arr[0] as i8),
-            0xA => DataType::UCHAR(arr[0] as u8),
-            0xB => DataType::BCHAR(arr[0] as u8),
+            0xA => DataType::UCHAR(arr[0]),
+            0xB => DataType::BCHAR(arr[0]),
             0xC => DataType::SHORT2(as_u16(arr) as i16, as_u16(&arr[2..]) as i16),
-            0xD => DataType::USHORT2(as_u16(arr), as_u16(arr)),
+            0xD => DataType::USHORT2(as_u16(arr), as_u16(&arr[2..])),
             0xE => DataType::BSHORT2(as_u16(arr), as_u16(&arr[2..])),
             0xF => DataType::CHAR4(arr[0] as i8, arr[1] as i8, arr[2] as i8, arr[3] as i8),
             0x10 => DataType::UCHAR4(arr[0], arr[1], arr[2], arr[3]),